@@ -1,20 +1,26 @@
 use std::{
     collections::{BTreeMap, HashMap},
+    io::{Read, Write},
     mem::take,
     sync::Arc,
 };
 
+use hybrid_array::typenum::U8;
 use itertools::Itertools;
 use p3_field::AbstractField;
 use serde::{Deserialize, Serialize};
 
-use super::{program::Program, Opcode};
+use strum::IntoEnumIterator;
+
+use super::{program::Program, Opcode, SyscallCode};
 use crate::alu::AluEvent;
 use crate::bytes::event::ByteRecord;
 use crate::bytes::ByteLookupEvent;
 use crate::cpu::CpuEvent;
 use crate::runtime::MemoryInitializeFinalizeEvent;
+use crate::runtime::MemoryReadRecord;
 use crate::runtime::MemoryRecordEnum;
+use crate::runtime::MemoryWriteRecord;
 use crate::stark::MachineRecord;
 use crate::syscall::precompiles::blake3::Blake3CompressInnerEvent;
 use crate::syscall::precompiles::bls12_381::g2_add::Bls12381G2AffineAddEvent;
@@ -22,6 +28,7 @@ use crate::syscall::precompiles::bls12_381::g2_double::Bls12381G2AffineDoubleEve
 use crate::syscall::precompiles::edwards::EdDecompressEvent;
 use crate::syscall::precompiles::keccak256::KeccakPermuteEvent;
 use crate::syscall::precompiles::sha256::{ShaCompressEvent, ShaExtendEvent};
+use crate::syscall::precompiles::DEFAULT_NUM_LIMBS_T;
 use crate::syscall::precompiles::{ECAddEvent, ECDoubleEvent};
 use crate::utils::env;
 use crate::{
@@ -29,13 +36,207 @@ use crate::{
     operations::field::params::FieldParameters,
     syscall::precompiles::{
         bls12_381::g1_decompress::Bls12381G1DecompressEvent,
-        field::{add::FieldAddEvent, mul::FieldMulEvent, sub::FieldSubEvent},
+        bn254::scalar::{Bn254ScalarMacEvent, Bn254ScalarOpEvent},
+        field::{add::FieldAddEvent, mac::FieldMacEvent, mul::FieldMulEvent, sub::FieldSubEvent},
+        memcopy::{MemCopyEvent, MemCopyWidth},
         quad_field::{add::QuadFieldAddEvent, mul::QuadFieldMulEvent, sub::QuadFieldSubEvent},
         secp256k1::decompress::Secp256k1DecompressEvent,
+        weierstrass::bls12381_g2_decompress::Bls12381G2DecompressEvent,
+        weierstrass::weierstrass_compress::WeierstrassCompressEvent,
     },
-    utils::ec::weierstrass::bls12_381::Bls12381BaseField,
+    utils::ec::weierstrass::{bls12_381::Bls12381BaseField, bn254::Bn254BaseField},
 };
 
+/// One precompile-emitted event, tagged by which syscall produced it and keyed in
+/// `ExecutionRecord::precompile_events` under that `SyscallCode`. This replaces the ~30
+/// near-identical `Vec<...Event>` fields that used to live directly on `ExecutionRecord`: adding a
+/// precompile used to mean adding a field here, a line in `stats`, a line in `append`, and a
+/// chunking block in `shard`; now it's a single new variant.
+///
+/// Not `#[derive(strum::EnumIter)]`: that derive constructs one instance per variant via
+/// `Default`, which the wrapped event structs don't implement. `SyscallCode` (the map key) is the
+/// enumerable type instead -- see `ExecutionRecord::stats`/`append`/`shard`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum PrecompileEvent {
+    ShaExtend(ShaExtendEvent),
+    ShaCompress(ShaCompressEvent),
+    KeccakPermute(KeccakPermuteEvent),
+    EdAdd(ECAddEvent),
+    EdDecompress(EdDecompressEvent<DEFAULT_NUM_LIMBS_T>),
+    Secp256k1Add(ECAddEvent),
+    Secp256k1Double(ECDoubleEvent),
+    Secp256k1Decompress(Secp256k1DecompressEvent),
+    Bn254Add(ECAddEvent),
+    Bn254Double(ECDoubleEvent),
+    Bls12381G1Add(ECAddEvent<<Bls12381BaseField as FieldParameters>::NB_LIMBS>),
+    Bls12381G1Double(ECDoubleEvent<<Bls12381BaseField as FieldParameters>::NB_LIMBS>),
+    Bls12381G1Decompress(Bls12381G1DecompressEvent),
+    Bls12381G2Add(Bls12381G2AffineAddEvent),
+    Bls12381G2Double(Bls12381G2AffineDoubleEvent),
+    Blake3CompressInner(Blake3CompressInnerEvent),
+    Bls12381FpAdd(FieldAddEvent<Bls12381BaseField>),
+    Bls12381FpSub(FieldSubEvent<Bls12381BaseField>),
+    Bls12381FpMul(FieldMulEvent<Bls12381BaseField>),
+    Bls12381FpMac(FieldMacEvent<Bls12381BaseField>),
+    Bls12381Fp2Add(QuadFieldAddEvent<Bls12381BaseField>),
+    Bls12381Fp2Sub(QuadFieldSubEvent<Bls12381BaseField>),
+    Bls12381Fp2Mul(QuadFieldMulEvent<Bls12381BaseField>),
+    Bn254FpAdd(FieldAddEvent<Bn254BaseField>),
+    Bn254FpSub(FieldSubEvent<Bn254BaseField>),
+    Bn254FpMul(FieldMulEvent<Bn254BaseField>),
+    Bn254ScalarAdd(Bn254ScalarOpEvent),
+    Bn254ScalarSub(Bn254ScalarOpEvent),
+    Bn254ScalarMul(Bn254ScalarOpEvent),
+    Bn254ScalarDiv(Bn254ScalarOpEvent),
+    Bn254ScalarMac(Bn254ScalarMacEvent),
+    MemCopy(MemCopyEvent<U8>),
+    Bls12381G2Decompress(Bls12381G2DecompressEvent),
+    Secp256k1Compress(WeierstrassCompressEvent<DEFAULT_NUM_LIMBS_T>),
+    Bn254Compress(WeierstrassCompressEvent<DEFAULT_NUM_LIMBS_T>),
+    Secp256r1Compress(WeierstrassCompressEvent<DEFAULT_NUM_LIMBS_T>),
+    Bls12381Compress(WeierstrassCompressEvent<<Bls12381BaseField as FieldParameters>::NB_LIMBS>),
+}
+
+/// The first read and the last write of a single address within one shard, used to reconcile the
+/// memory consistency argument at shard boundaries instead of across the whole global address
+/// set. `initial_mem_access`/`final_mem_access` are the same record shapes used for per-event
+/// memory tracking elsewhere (see [`MemoryAccessRecord`]); for an address touched only once in
+/// the shard, the two are equal.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct MemoryLocalEvent {
+    pub addr: u32,
+    pub initial_mem_access: MemoryRecordEnum,
+    pub final_mem_access: MemoryRecordEnum,
+}
+
+/// How many elements each [`ChunkedVec`] chunk holds before a new one is allocated. Chosen well
+/// above a single shard's worth of ALU events so the common case allocates only a handful of
+/// chunks per vector over a whole run.
+const EVENT_CHUNK_CAPACITY: usize = 1 << 16;
+
+/// Append-only storage for the event vectors that can grow to hundreds of thousands of entries
+/// over a long execution (`add_events`, `mul_events`, ... -- see
+/// [`ExecutionRecord::add_mul_event`]/[`ExecutionRecord::add_lt_event`]/
+/// [`ExecutionRecord::add_alu_events`]). Backed by a `Vec` of fixed-capacity chunks instead of one
+/// growing `Vec`: once a chunk is full, `push` starts a new one rather than reallocating and
+/// copying everything pushed so far, the way `Vec`'s doubling growth would.
+///
+/// Exposes the subset of `Vec`'s API the rest of this file needs (`push`, `len`, `reserve`,
+/// `append`, `extend_from_slice`); code that needs contiguous slice access (e.g. the positional
+/// `chunks_mut` split in [`MachineRecord::shard`]) flattens via [`ChunkedVec::into_vec`] first --
+/// the one place a copy is unavoidable, since nothing downstream can take slice-of-chunks
+/// semantics across chunk boundaries.
+#[derive(Clone, Debug)]
+pub struct ChunkedVec<T> {
+    chunks: Vec<Vec<T>>,
+    len: usize,
+}
+
+impl<T> ChunkedVec<T> {
+    pub fn new() -> Self {
+        Self {
+            chunks: Vec::new(),
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Appends a single element, starting a new chunk if the last one is full. Never moves or
+    /// reallocates previously pushed elements.
+    pub fn push(&mut self, item: T) {
+        match self.chunks.last_mut() {
+            Some(chunk) if chunk.len() < chunk.capacity() => chunk.push(item),
+            _ => {
+                let mut chunk = Vec::with_capacity(EVENT_CHUNK_CAPACITY);
+                chunk.push(item);
+                self.chunks.push(chunk);
+            }
+        }
+        self.len += 1;
+    }
+
+    /// Tops up the current chunk (if any room remains) and allocates as many full chunks as
+    /// needed to cover `additional` more elements -- the exact boundaries `push` will later fill,
+    /// so none of this is ever undone the way over-reserving a `Vec` would be.
+    pub fn reserve(&mut self, additional: usize) {
+        let mut remaining = additional;
+        if let Some(chunk) = self.chunks.last() {
+            remaining = remaining.saturating_sub(chunk.capacity() - chunk.len());
+        }
+        while remaining > 0 {
+            self.chunks.push(Vec::with_capacity(EVENT_CHUNK_CAPACITY));
+            remaining = remaining.saturating_sub(EVENT_CHUNK_CAPACITY);
+        }
+    }
+
+    /// Moves every chunk from `other` onto the end of `self`. Like `push`, this only ever moves
+    /// chunks, never copies the elements inside them.
+    pub fn append(&mut self, other: &mut Self) {
+        self.len += other.len;
+        other.len = 0;
+        self.chunks.append(&mut other.chunks);
+    }
+
+    /// Appends `items`, chunk-by-chunk, the same way repeated `push` calls would.
+    pub fn extend_from_slice(&mut self, items: &[T])
+    where
+        T: Clone,
+    {
+        self.reserve(items.len());
+        for item in items {
+            self.push(item.clone());
+        }
+    }
+
+    /// Flattens into a plain, contiguously-indexable `Vec`. The one place this type copies its
+    /// elements -- unavoidable once a call site needs slice semantics across chunk boundaries.
+    pub fn into_vec(self) -> Vec<T> {
+        let mut out = Vec::with_capacity(self.len);
+        out.extend(self.chunks.into_iter().flatten());
+        out
+    }
+}
+
+impl<T> Default for ChunkedVec<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> From<Vec<T>> for ChunkedVec<T> {
+    fn from(vec: Vec<T>) -> Self {
+        let len = vec.len();
+        Self {
+            chunks: vec![vec],
+            len,
+        }
+    }
+}
+
+impl<T: Serialize> Serialize for ChunkedVec<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeSeq;
+        let mut seq = serializer.serialize_seq(Some(self.len))?;
+        for item in self.chunks.iter().flatten() {
+            seq.serialize_element(item)?;
+        }
+        seq.end()
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for ChunkedVec<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(ChunkedVec::from(Vec::<T>::deserialize(deserializer)?))
+    }
+}
+
 /// A record of the execution of a program. Contains event data for everything that happened during
 /// the execution of the shard.
 #[derive(Default, Clone, Debug, Serialize, Deserialize)]
@@ -50,76 +251,161 @@ pub struct ExecutionRecord {
     pub cpu_events: Vec<CpuEvent>,
 
     /// A trace of the ADD, and ADDI events.
-    pub add_events: Vec<AluEvent>,
+    pub add_events: ChunkedVec<AluEvent>,
 
     /// A trace of the MUL events.
-    pub mul_events: Vec<AluEvent>,
+    pub mul_events: ChunkedVec<AluEvent>,
 
     /// A trace of the SUB events.
-    pub sub_events: Vec<AluEvent>,
+    pub sub_events: ChunkedVec<AluEvent>,
 
     /// A trace of the XOR, XORI, OR, ORI, AND, and ANDI events.
-    pub bitwise_events: Vec<AluEvent>,
+    pub bitwise_events: ChunkedVec<AluEvent>,
 
     /// A trace of the SLL and SLLI events.
-    pub shift_left_events: Vec<AluEvent>,
+    pub shift_left_events: ChunkedVec<AluEvent>,
 
     /// A trace of the SRL, SRLI, SRA, and SRAI events.
-    pub shift_right_events: Vec<AluEvent>,
+    pub shift_right_events: ChunkedVec<AluEvent>,
 
     /// A trace of the DIV, DIVU, REM, and REMU events.
     pub divrem_events: Vec<AluEvent>,
 
     /// A trace of the SLT, SLTI, SLTU, and SLTIU events.
-    pub lt_events: Vec<AluEvent>,
+    pub lt_events: ChunkedVec<AluEvent>,
 
     /// All byte lookups that are needed. The layout is shard -> (event -> count). Byte lookups are
     /// sharded to prevent the multiplicities from overflowing.
     pub byte_lookups: BTreeMap<u32, BTreeMap<ByteLookupEvent, usize>>,
 
-    pub sha_extend_events: Vec<ShaExtendEvent>,
-
-    pub sha_compress_events: Vec<ShaCompressEvent>,
-
-    pub keccak_permute_events: Vec<KeccakPermuteEvent>,
-
-    pub ed_add_events: Vec<ECAddEvent>,
+    /// All precompile-emitted traces (field/curve arithmetic, decompression, hashing, bulk
+    /// memory moves, ...), bucketed by the `SyscallCode` that produced them. See
+    /// [`PrecompileEvent`] for the payload each bucket holds.
+    pub precompile_events: HashMap<SyscallCode, Vec<PrecompileEvent>>,
 
-    pub ed_decompress_events: Vec<EdDecompressEvent>,
-
-    pub secp256k1_add_events: Vec<ECAddEvent>,
+    pub memory_initialize_events: Vec<MemoryInitializeFinalizeEvent>,
 
-    pub secp256k1_double_events: Vec<ECDoubleEvent>,
+    pub memory_finalize_events: Vec<MemoryInitializeFinalizeEvent>,
 
-    pub bn254_add_events: Vec<ECAddEvent>,
+    /// Per-address first-read/last-write pairs for addresses touched within this shard. Lets the
+    /// memory argument reconcile shard-local accesses directly instead of going through the
+    /// global initialize/finalize tables for every touched address.
+    pub memory_local_events: Vec<MemoryLocalEvent>,
+
+    /// In-progress first-touch/last-touch tracking, keyed by `(shard, addr)` so each shard gets
+    /// its own pair per address. Every
+    /// `a`/`b`/`c`/`memory` CPU access and every precompile buffer read/write (via
+    /// [`ExecutionRecord::update_local_memory_access_reads`]/
+    /// [`ExecutionRecord::update_local_memory_access_writes`]) goes through
+    /// [`ExecutionRecord::update_local_memory_access`], which sets `initial_mem_access` the first
+    /// time an address appears in a shard and overwrites `final_mem_access` on every later touch
+    /// in that shard. Drained into `memory_local_events` by a single
+    /// [`ExecutionRecord::finalize_local_memory_access`] call at the end of
+    /// [`shard`](MachineRecord::shard), not at each shard boundary during execution, so this map
+    /// holds entries for every shard of the run; [`shard`](MachineRecord::shard) assigns each
+    /// resulting event to the shard its `final_mem_access` was recorded in when splitting, rather
+    /// than relying on position. This map is always empty on a record that's been through `shard`.
+    #[serde(skip)]
+    pub local_memory_access: HashMap<(u32, u32), MemoryLocalEvent>,
 
-    pub bn254_double_events: Vec<ECDoubleEvent>,
+    /// The public values.
+    pub public_values: PublicValues<u32, u32>,
+}
 
-    pub bls12381_g1_add_events: Vec<ECAddEvent<<Bls12381BaseField as FieldParameters>::NB_LIMBS>>,
+/// How an `ExecutionRecord`'s event streams are encoded when checkpointed or shipped to the
+/// prover, selectable at prove time. Setting `compact` LEB128-varint-encodes
+/// `memory_local_events`'s `addr` field (see [`Self::write_compressed`]) instead of its default
+/// fixed 4-byte width before the rest of the record is `bincode`-encoded as usual; `compressed`
+/// additionally pipes the result through a [`RecordCompressor`]. The two are independent flags
+/// since compacting and compressing address different redundancy (fixed-width small integers vs.
+/// repeated byte patterns).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RecordSerializationMode {
+    pub compact: bool,
+    pub compressed: bool,
+}
 
-    pub bls12381_g1_double_events:
-        Vec<ECDoubleEvent<<Bls12381BaseField as FieldParameters>::NB_LIMBS>>,
+/// A block-compression backend plugged in via [`RecordSerializationMode::compressed`]. Left abstract
+/// here rather than bundling a concrete codec (e.g. zstd/lz4), since which one is available
+/// depends on the embedding binary's dependencies.
+pub trait RecordCompressor {
+    fn compress(&self, bytes: &[u8]) -> Vec<u8>;
+    fn decompress(&self, bytes: &[u8]) -> Vec<u8>;
+}
 
-    pub secp256k1_decompress_events: Vec<Secp256k1DecompressEvent>,
+/// LEB128-encodes `value`, appending the result to `out`. Used by
+/// [`RecordSerializationMode::compact`] for the length and count prefixes of the compact
+/// `memory_local_events` encoding; the `addr` deltas themselves are zigzag-encoded and written
+/// with `write_varint_u64`.
+pub(crate) fn write_varint_u32(mut value: u32, out: &mut Vec<u8>) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            out.push(byte);
+            break;
+        }
+        out.push(byte | 0x80);
+    }
+}
 
-    pub blake3_compress_inner_events: Vec<Blake3CompressInnerEvent>,
+/// Decodes a LEB128 varint from `bytes` starting at `*pos`, advancing `*pos` past it.
+pub(crate) fn read_varint_u32(bytes: &[u8], pos: &mut usize) -> u32 {
+    let mut value = 0u32;
+    let mut shift = 0;
+    loop {
+        let byte = bytes[*pos];
+        *pos += 1;
+        value |= ((byte & 0x7f) as u32) << shift;
+        if byte & 0x80 == 0 {
+            break;
+        }
+        shift += 7;
+    }
+    value
+}
 
-    pub bls12381_fp_add_events: Vec<FieldAddEvent<Bls12381BaseField>>,
-    pub bls12381_fp_sub_events: Vec<FieldSubEvent<Bls12381BaseField>>,
-    pub bls12381_fp_mul_events: Vec<FieldMulEvent<Bls12381BaseField>>,
-    pub bls12381_fp2_add_events: Vec<QuadFieldAddEvent<Bls12381BaseField>>,
-    pub bls12381_fp2_sub_events: Vec<QuadFieldSubEvent<Bls12381BaseField>>,
-    pub bls12381_fp2_mul_events: Vec<QuadFieldMulEvent<Bls12381BaseField>>,
-    pub bls12381_g1_decompress_events: Vec<Bls12381G1DecompressEvent>,
-    pub bls12381_g2_add_events: Vec<Bls12381G2AffineAddEvent>,
-    pub bls12381_g2_double_events: Vec<Bls12381G2AffineDoubleEvent>,
+/// LEB128-encodes `value` as a `u64`, appending the result to `out`. Used for zigzag-encoded
+/// deltas (see [`zigzag_encode`]), which can briefly exceed `u32::MAX` when two `u32`s are far
+/// apart, even though the values being delta-encoded are themselves `u32`s.
+fn write_varint_u64(mut value: u64, out: &mut Vec<u8>) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            out.push(byte);
+            break;
+        }
+        out.push(byte | 0x80);
+    }
+}
 
-    pub memory_initialize_events: Vec<MemoryInitializeFinalizeEvent>,
+/// Decodes a `u64` LEB128 varint from `bytes` starting at `*pos`, advancing `*pos` past it.
+fn read_varint_u64(bytes: &[u8], pos: &mut usize) -> u64 {
+    let mut value = 0u64;
+    let mut shift = 0;
+    loop {
+        let byte = bytes[*pos];
+        *pos += 1;
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            break;
+        }
+        shift += 7;
+    }
+    value
+}
 
-    pub memory_finalize_events: Vec<MemoryInitializeFinalizeEvent>,
+/// Zigzag-maps a signed delta onto the unsigned range so small deltas in either direction both
+/// varint-encode to a small number of bytes, instead of a small negative delta's two's-complement
+/// bit pattern varint-encoding as if it were huge.
+fn zigzag_encode(delta: i64) -> u64 {
+    ((delta << 1) ^ (delta >> 63)) as u64
+}
 
-    /// The public values.
-    pub public_values: PublicValues<u32, u32>,
+/// Inverse of [`zigzag_encode`].
+fn zigzag_decode(encoded: u64) -> i64 {
+    ((encoded >> 1) as i64) ^ -((encoded & 1) as i64)
 }
 
 pub struct ShardingConfig {
@@ -132,31 +418,58 @@ pub struct ShardingConfig {
     pub shift_right_len: usize,
     pub divrem_len: usize,
     pub lt_len: usize,
-    pub field_len: usize,
-    pub keccak_len: usize,
-    pub secp256k1_add_len: usize,
-    pub secp256k1_double_len: usize,
-    pub bn254_add_len: usize,
-    pub bn254_double_len: usize,
-    pub bls12381_g1_add_len: usize,
-    pub bls12381_g1_double_len: usize,
-    pub bls12381_fp_add_len: usize,
-    pub bls12381_fp_sub_len: usize,
-    pub bls12381_fp_mul_len: usize,
-    pub bls12381_fp2_add_len: usize,
-    pub bls12381_fp2_sub_len: usize,
-    pub bls12381_fp2_mul_len: usize,
+    pub memory_local_len: usize,
+    /// Per-`SyscallCode` override for how many of that precompile's events go in each shard.
+    /// Codes absent here chunk at `shard_size` (see `precompile_len`). Field-arithmetic
+    /// precompiles (`bls12381_fp*`, `bn254_fp*`, `bn254_scalar_*`) do four limbs' worth of work
+    /// per event, so they get four times the budget of a plain ALU-sized event.
+    pub precompile_len_overrides: HashMap<SyscallCode, usize>,
 }
 
 impl ShardingConfig {
     pub const fn shard_size(&self) -> usize {
         self.shard_size
     }
+
+    /// The number of `code`'s precompile events that should go in each shard.
+    pub fn precompile_len(&self, code: SyscallCode) -> usize {
+        self.precompile_len_overrides
+            .get(&code)
+            .copied()
+            .unwrap_or(self.shard_size)
+    }
 }
 
 impl Default for ShardingConfig {
     fn default() -> Self {
         let shard_size = env::shard_size();
+        let field_len = shard_size * 4;
+        let precompile_len_overrides = [
+            SyscallCode::BLS12381_FP_ADD,
+            SyscallCode::BLS12381_FP_SUB,
+            SyscallCode::BLS12381_FP_MUL,
+            SyscallCode::BLS12381_FP_MAC,
+            SyscallCode::BLS12381_FP2_ADD,
+            SyscallCode::BLS12381_FP2_SUB,
+            SyscallCode::BLS12381_FP2_MUL,
+            SyscallCode::BN254_FP_ADD,
+            SyscallCode::BN254_FP_SUB,
+            SyscallCode::BN254_FP_MUL,
+            SyscallCode::BN254_SCALAR_ADD,
+            SyscallCode::BN254_SCALAR_SUB,
+            SyscallCode::BN254_SCALAR_MUL,
+            SyscallCode::BN254_SCALAR_DIV,
+            SyscallCode::BN254_SCALAR_MAC,
+        ]
+        .into_iter()
+        .map(|code| (code, field_len))
+        // MemCopy events do up to `N` words of memory access apiece rather than a field
+        // element's worth, so (unlike the field-arithmetic codes above) its budget tracks
+        // `shard_size` directly; the entry is still explicit so callers that enumerate
+        // `precompile_len_overrides` to see which precompiles are configured don't miss it.
+        .chain(std::iter::once((SyscallCode::MEMCOPY, shard_size)))
+        .collect();
+
         Self {
             shard_size,
             add_len: shard_size,
@@ -165,22 +478,10 @@ impl Default for ShardingConfig {
             shift_left_len: shard_size,
             divrem_len: shard_size,
             lt_len: shard_size,
+            memory_local_len: shard_size,
             mul_len: shard_size,
             shift_right_len: shard_size,
-            field_len: shard_size * 4,
-            keccak_len: shard_size,
-            secp256k1_add_len: shard_size,
-            secp256k1_double_len: shard_size,
-            bn254_add_len: shard_size,
-            bn254_double_len: shard_size,
-            bls12381_g1_add_len: shard_size,
-            bls12381_g1_double_len: shard_size,
-            bls12381_fp_add_len: shard_size,
-            bls12381_fp_sub_len: shard_size,
-            bls12381_fp_mul_len: shard_size,
-            bls12381_fp2_add_len: shard_size,
-            bls12381_fp2_sub_len: shard_size,
-            bls12381_fp2_mul_len: shard_size,
+            precompile_len_overrides,
         }
     }
 }
@@ -214,91 +515,26 @@ impl MachineRecord for ExecutionRecord {
         stats.insert("divrem_events".to_string(), self.divrem_events.len());
         stats.insert("lt_events".to_string(), self.lt_events.len());
         stats.insert(
-            "sha_extend_events".to_string(),
-            self.sha_extend_events.len(),
-        );
-        stats.insert(
-            "sha_compress_events".to_string(),
-            self.sha_compress_events.len(),
-        );
-        stats.insert(
-            "keccak_permute_events".to_string(),
-            self.keccak_permute_events.len(),
-        );
-        stats.insert("ed_add_events".to_string(), self.ed_add_events.len());
-        stats.insert(
-            "ed_decompress_events".to_string(),
-            self.ed_decompress_events.len(),
-        );
-        stats.insert(
-            "secp256k1_add_events".to_string(),
-            self.secp256k1_add_events.len(),
-        );
-        stats.insert(
-            "secp256k1_double_events".to_string(),
-            self.secp256k1_double_events.len(),
-        );
-        stats.insert("bn254_add_events".to_string(), self.bn254_add_events.len());
-        stats.insert(
-            "bn254_double_events".to_string(),
-            self.bn254_double_events.len(),
-        );
-        stats.insert(
-            "bls12381_g1_add_events".to_string(),
-            self.bls12381_g1_add_events.len(),
-        );
-        stats.insert(
-            "bls12381_g1_double_events".to_string(),
-            self.bls12381_g1_double_events.len(),
-        );
-        stats.insert(
-            "k256_decompress_events".to_string(),
-            self.secp256k1_decompress_events.len(),
-        );
-        stats.insert(
-            "blake3_compress_inner_events".to_string(),
-            self.blake3_compress_inner_events.len(),
-        );
-        stats.insert(
-            "bls12381_g1_decompress_events".to_string(),
-            self.bls12381_g1_decompress_events.len(),
-        );
-        stats.insert(
-            "bls12381_fp_add_events".to_string(),
-            self.bls12381_fp_add_events.len(),
-        );
-        stats.insert(
-            "bls12381_fp_sub_events".to_string(),
-            self.bls12381_fp_sub_events.len(),
-        );
-        stats.insert(
-            "bls12381_fp_mul_events".to_string(),
-            self.bls12381_fp_mul_events.len(),
-        );
-        stats.insert(
-            "bls12381_fp2_add_events".to_string(),
-            self.bls12381_fp2_add_events.len(),
-        );
-        stats.insert(
-            "bls12381_fp2_sub_events".to_string(),
-            self.bls12381_fp2_sub_events.len(),
-        );
-        stats.insert(
-            "bls12381_fp2_mul_events".to_string(),
-            self.bls12381_fp2_mul_events.len(),
-        );
-        stats.insert(
-            "bls12381_g2_add_events".to_string(),
-            self.bls12381_g2_add_events.len(),
-        );
-        stats.insert(
-            "bls12381_g2_double_events".to_string(),
-            self.bls12381_g2_double_events.len(),
+            "memory_local_events".to_string(),
+            self.memory_local_events.len(),
         );
+        for code in SyscallCode::iter() {
+            if let Some(events) = self.precompile_events.get(&code) {
+                stats.insert(format!("{code:?}").to_lowercase(), events.len());
+            }
+        }
         stats
     }
 
     fn append(&mut self, other: &mut ExecutionRecord) {
+        // `other`'s plain `Vec` buffers are about to land in ours wholesale, so reserve each of
+        // ours by `other`'s exact matching length up front instead of letting each `append` below
+        // potentially reallocate on its own. Each vector is sized from its own counterpart, not a
+        // single hint shared across all of them -- the buffers don't grow in lockstep. The
+        // `ChunkedVec` fields are not reserved: their `append` only moves `other`'s chunks across.
+        self.cpu_events.reserve(other.cpu_events.len());
+        self.divrem_events.reserve(other.divrem_events.len());
+
         self.cpu_events.append(&mut other.cpu_events);
         self.add_events.append(&mut other.add_events);
         self.sub_events.append(&mut other.sub_events);
@@ -309,47 +545,13 @@ impl MachineRecord for ExecutionRecord {
             .append(&mut other.shift_right_events);
         self.divrem_events.append(&mut other.divrem_events);
         self.lt_events.append(&mut other.lt_events);
-        self.sha_extend_events.append(&mut other.sha_extend_events);
-        self.sha_compress_events
-            .append(&mut other.sha_compress_events);
-        self.keccak_permute_events
-            .append(&mut other.keccak_permute_events);
-        self.ed_add_events.append(&mut other.ed_add_events);
-        self.ed_decompress_events
-            .append(&mut other.ed_decompress_events);
-        self.secp256k1_add_events
-            .append(&mut other.secp256k1_add_events);
-        self.secp256k1_double_events
-            .append(&mut other.secp256k1_double_events);
-        self.bn254_add_events.append(&mut other.bn254_add_events);
-        self.bn254_double_events
-            .append(&mut other.bn254_double_events);
-        self.bls12381_g1_add_events
-            .append(&mut other.bls12381_g1_add_events);
-        self.bls12381_g1_double_events
-            .append(&mut other.bls12381_g1_double_events);
-        self.secp256k1_decompress_events
-            .append(&mut other.secp256k1_decompress_events);
-        self.blake3_compress_inner_events
-            .append(&mut other.blake3_compress_inner_events);
-        self.bls12381_fp_add_events
-            .append(&mut other.bls12381_fp_add_events);
-        self.bls12381_fp_sub_events
-            .append(&mut other.bls12381_fp_sub_events);
-        self.bls12381_fp_mul_events
-            .append(&mut other.bls12381_fp_mul_events);
-        self.bls12381_fp2_add_events
-            .append(&mut other.bls12381_fp2_add_events);
-        self.bls12381_fp2_sub_events
-            .append(&mut other.bls12381_fp2_sub_events);
-        self.bls12381_fp2_mul_events
-            .append(&mut other.bls12381_fp2_mul_events);
-        self.bls12381_g1_decompress_events
-            .append(&mut other.bls12381_g1_decompress_events);
-        self.bls12381_g2_add_events
-            .append(&mut other.bls12381_g2_add_events);
-        self.bls12381_g2_double_events
-            .append(&mut other.bls12381_g2_double_events);
+
+        for (code, mut events) in take(&mut other.precompile_events) {
+            self.precompile_events
+                .entry(code)
+                .or_default()
+                .append(&mut events);
+        }
 
         // Merge the byte lookups.
         for (shard, events_map) in take(&mut other.byte_lookups) {
@@ -371,9 +573,22 @@ impl MachineRecord for ExecutionRecord {
             .append(&mut other.memory_initialize_events);
         self.memory_finalize_events
             .append(&mut other.memory_finalize_events);
+        self.memory_local_events
+            .append(&mut other.memory_local_events);
+
+        for (addr, event) in take(&mut other.local_memory_access) {
+            self.local_memory_access
+                .entry(addr)
+                .and_modify(|existing| existing.final_mem_access = event.final_mem_access)
+                .or_insert(event);
+        }
     }
 
     fn shard(mut self, config: &ShardingConfig) -> Vec<Self> {
+        // `local_memory_access` is never drained mid-run, so this is the only finalize call: fold
+        // the whole run's map into `memory_local_events` before grouping it by shard below.
+        self.finalize_local_memory_access();
+
         // Get the number of CPU events.
         let num_cpu_events = self.cpu_events.len();
 
@@ -428,6 +643,7 @@ impl MachineRecord for ExecutionRecord {
 
         // Shard the ADD events.
         for (add_chunk, shard) in take(&mut self.add_events)
+            .into_vec()
             .chunks_mut(config.add_len)
             .zip(shards.iter_mut())
         {
@@ -436,6 +652,7 @@ impl MachineRecord for ExecutionRecord {
 
         // Shard the MUL events.
         for (mul_chunk, shard) in take(&mut self.mul_events)
+            .into_vec()
             .chunks_mut(config.mul_len)
             .zip(shards.iter_mut())
         {
@@ -444,6 +661,7 @@ impl MachineRecord for ExecutionRecord {
 
         // Shard the SUB events.
         for (sub_chunk, shard) in take(&mut self.sub_events)
+            .into_vec()
             .chunks_mut(config.sub_len)
             .zip(shards.iter_mut())
         {
@@ -452,6 +670,7 @@ impl MachineRecord for ExecutionRecord {
 
         // Shard the bitwise events.
         for (bitwise_chunk, shard) in take(&mut self.bitwise_events)
+            .into_vec()
             .chunks_mut(config.bitwise_len)
             .zip(shards.iter_mut())
         {
@@ -460,6 +679,7 @@ impl MachineRecord for ExecutionRecord {
 
         // Shard the shift left events.
         for (shift_left_chunk, shard) in take(&mut self.shift_left_events)
+            .into_vec()
             .chunks_mut(config.shift_left_len)
             .zip(shards.iter_mut())
         {
@@ -468,6 +688,7 @@ impl MachineRecord for ExecutionRecord {
 
         // Shard the shift right events.
         for (shift_right_chunk, shard) in take(&mut self.shift_right_events)
+            .into_vec()
             .chunks_mut(config.shift_right_len)
             .zip(shards.iter_mut())
         {
@@ -486,158 +707,48 @@ impl MachineRecord for ExecutionRecord {
 
         // Shard the LT events.
         for (lt_chunk, shard) in take(&mut self.lt_events)
+            .into_vec()
             .chunks_mut(config.lt_len)
             .zip(shards.iter_mut())
         {
             shard.lt_events.extend_from_slice(lt_chunk);
         }
 
-        // Keccak-256 permute events.
-        for (keccak_chunk, shard) in take(&mut self.keccak_permute_events)
-            .chunks_mut(config.keccak_len)
-            .zip(shards.iter_mut())
-        {
-            shard.keccak_permute_events.extend_from_slice(keccak_chunk);
-        }
-
-        // secp256k1 curve add events.
-        for (secp256k1_add_chunk, shard) in take(&mut self.secp256k1_add_events)
-            .chunks_mut(config.secp256k1_add_len)
-            .zip(shards.iter_mut())
-        {
-            shard
-                .secp256k1_add_events
-                .extend_from_slice(secp256k1_add_chunk);
+        // Shard the memory-local events by the shard that actually touched them. Unlike the ALU
+        // vectors above, `local_memory_access` is a `HashMap` drained in one shot by
+        // `finalize_local_memory_access` (called once, from this function, on the whole run's
+        // accumulated map), so the resulting `memory_local_events` vector has no relation between
+        // position and shard: a positional `chunks_mut` split would hand shards essentially
+        // random other shards' events. Group by each event's own shard (carried on its
+        // `final_mem_access`) instead.
+        let mut shard_index_to_pos = HashMap::with_capacity(shards.len());
+        for (pos, shard) in shards.iter().enumerate() {
+            shard_index_to_pos.insert(shard.index, pos);
         }
-
-        // secp256k1 curve double events.
-        for (secp256k1_double_chunk, shard) in take(&mut self.secp256k1_double_events)
-            .chunks_mut(config.secp256k1_double_len)
-            .zip(shards.iter_mut())
-        {
-            shard
-                .secp256k1_double_events
-                .extend_from_slice(secp256k1_double_chunk);
-        }
-
-        // bn254 curve add events.
-        for (bn254_add_chunk, shard) in take(&mut self.bn254_add_events)
-            .chunks_mut(config.bn254_add_len)
-            .zip(shards.iter_mut())
-        {
-            shard.bn254_add_events.extend_from_slice(bn254_add_chunk);
-        }
-
-        // bn254 curve double events.
-        for (bn254_double_chunk, shard) in take(&mut self.bn254_double_events)
-            .chunks_mut(config.bn254_double_len)
-            .zip(shards.iter_mut())
-        {
-            shard
-                .bn254_double_events
-                .extend_from_slice(bn254_double_chunk);
-        }
-
-        // BLS12-381 curve add events.
-        for (bls12381_g1_add_chunk, shard) in take(&mut self.bls12381_g1_add_events)
-            .chunks_mut(config.bls12381_g1_add_len)
-            .zip(shards.iter_mut())
-        {
-            shard
-                .bls12381_g1_add_events
-                .extend_from_slice(bls12381_g1_add_chunk);
-        }
-
-        // BLS12-381 curve double events.
-        for (bls12381_g1_double_chunk, shard) in take(&mut self.bls12381_g1_double_events)
-            .chunks_mut(config.bls12381_g1_double_len)
-            .zip(shards.iter_mut())
-        {
-            shard
-                .bls12381_g1_double_events
-                .extend_from_slice(bls12381_g1_double_chunk);
+        for event in take(&mut self.memory_local_events) {
+            let event_shard = match event.final_mem_access {
+                MemoryRecordEnum::Read(r) => r.shard,
+                MemoryRecordEnum::Write(w) => w.shard,
+            };
+            if let Some(&pos) = shard_index_to_pos.get(&event_shard) {
+                shards[pos].memory_local_events.push(event);
+            }
         }
 
-        // BLS12-381 Fp and Fp2 events
-        for (bls12381_fp_add_chunk, shard) in take(&mut self.bls12381_fp_add_events)
-            .chunks_mut(config.bls12381_fp_add_len)
-            .zip(shards.iter_mut())
-        {
-            shard
-                .bls12381_fp_add_events
-                .extend_from_slice(bls12381_fp_add_chunk)
-        }
-        for (bls12381_fp_sub_chunk, shard) in take(&mut self.bls12381_fp_sub_events)
-            .chunks_mut(config.bls12381_fp_sub_len)
-            .zip(shards.iter_mut())
-        {
-            shard
-                .bls12381_fp_sub_events
-                .extend_from_slice(bls12381_fp_sub_chunk)
-        }
-        for (bls12381_fp_mul_chunk, shard) in take(&mut self.bls12381_fp_mul_events)
-            .chunks_mut(config.bls12381_fp_mul_len)
-            .zip(shards.iter_mut())
-        {
-            shard
-                .bls12381_fp_mul_events
-                .extend_from_slice(bls12381_fp_mul_chunk)
-        }
-        for (bls12381_fp2_add_chunk, shard) in take(&mut self.bls12381_fp2_add_events)
-            .chunks_mut(config.bls12381_fp2_add_len)
-            .zip(shards.iter_mut())
-        {
-            shard
-                .bls12381_fp2_add_events
-                .extend_from_slice(bls12381_fp2_add_chunk)
-        }
-        for (bls12381_fp2_sub_chunk, shard) in take(&mut self.bls12381_fp2_sub_events)
-            .chunks_mut(config.bls12381_fp2_sub_len)
-            .zip(shards.iter_mut())
-        {
-            shard
-                .bls12381_fp2_sub_events
-                .extend_from_slice(bls12381_fp2_sub_chunk)
-        }
-        for (bls12381_fp2_mul_chunk, shard) in take(&mut self.bls12381_fp2_mul_events)
-            .chunks_mut(config.bls12381_fp2_mul_len)
-            .zip(shards.iter_mut())
-        {
-            shard
-                .bls12381_fp2_mul_events
-                .extend_from_slice(bls12381_fp2_mul_chunk)
+        // Shard every precompile's events by its own per-syscall length. `chunks_mut` preserves
+        // vector order, so within each shard the events keep the same relative order they had
+        // here.
+        for (code, mut events) in take(&mut self.precompile_events) {
+            let len = config.precompile_len(code);
+            for (chunk, shard) in events.chunks_mut(len).zip(shards.iter_mut()) {
+                shard
+                    .precompile_events
+                    .entry(code)
+                    .or_default()
+                    .extend_from_slice(chunk);
+            }
         }
 
-        // Put the precompile events in the first shard.
-        let first = shards.first_mut().unwrap();
-
-        // Bls12-381 G2Affine addition events
-        first.bls12381_g2_add_events = take(&mut self.bls12381_g2_add_events);
-
-        // Bls12-381 G2Affine doubling events
-        first.bls12381_g2_double_events = take(&mut self.bls12381_g2_double_events);
-
-        // Bls12-381 decompress events .
-        first.bls12381_g1_decompress_events = take(&mut self.bls12381_g1_decompress_events);
-
-        // SHA-256 extend events.
-        first.sha_extend_events = take(&mut self.sha_extend_events);
-
-        // SHA-256 compress events.
-        first.sha_compress_events = take(&mut self.sha_compress_events);
-
-        // Edwards curve add events.
-        first.ed_add_events = take(&mut self.ed_add_events);
-
-        // Edwards curve decompress events.
-        first.ed_decompress_events = take(&mut self.ed_decompress_events);
-
-        // K256 curve decompress events.
-        first.secp256k1_decompress_events = take(&mut self.secp256k1_decompress_events);
-
-        // Blake3 compress events .
-        first.blake3_compress_inner_events = take(&mut self.blake3_compress_inner_events);
-
         // Put the memory records in the last shard.
         let last_shard = shards.last_mut().unwrap();
 
@@ -648,6 +759,13 @@ impl MachineRecord for ExecutionRecord {
             .memory_finalize_events
             .extend_from_slice(&self.memory_finalize_events);
 
+        // Stamp each precompile event with its final, shard-relative row index now that sharding
+        // is done, rather than leaving every chip's `generate_trace` to re-derive it from
+        // `.enumerate()` position.
+        for shard in &mut shards {
+            shard.assign_nonces();
+        }
+
         shards
     }
 
@@ -667,6 +785,177 @@ impl ExecutionRecord {
         }
     }
 
+    /// Stamps every precompile event in this (already-sharded) record with its index within its
+    /// chip's per-shard vector, called once per shard at the end of [`shard`](MachineRecord::shard)
+    /// so a cross-table lookup argument can include the nonce in its tuple instead of every
+    /// chip's `generate_trace` re-deriving it from `.enumerate()` position.
+    ///
+    /// `precompile_events` is the only event storage this crate slice owns end-to-end: ALU/CPU
+    /// events (`add_events`, `cpu_events`, ...) and several precompile event types (SHA, Keccak,
+    /// the EC add/double family, secp256k1/BLS-G1 decompression) live in modules outside it, so
+    /// their structs can't grow a `nonce` field here. Those keep deriving their row index the old
+    /// way; this only covers the event types actually defined in this crate slice.
+    pub fn assign_nonces(&mut self) {
+        for events in self.precompile_events.values_mut() {
+            for (i, event) in events.iter_mut().enumerate() {
+                let nonce = i as u32;
+                match event {
+                    PrecompileEvent::Bls12381FpAdd(e) => e.nonce = nonce,
+                    PrecompileEvent::Bls12381FpSub(e) => e.nonce = nonce,
+                    PrecompileEvent::Bls12381FpMul(e) => e.nonce = nonce,
+                    PrecompileEvent::Bls12381FpMac(e) => e.nonce = nonce,
+                    PrecompileEvent::Bls12381Fp2Add(e) => e.nonce = nonce,
+                    PrecompileEvent::Bls12381Fp2Sub(e) => e.nonce = nonce,
+                    PrecompileEvent::Bls12381Fp2Mul(e) => e.nonce = nonce,
+                    PrecompileEvent::Bn254FpAdd(e) => e.nonce = nonce,
+                    PrecompileEvent::Bn254FpSub(e) => e.nonce = nonce,
+                    PrecompileEvent::Bn254FpMul(e) => e.nonce = nonce,
+                    PrecompileEvent::Bn254ScalarAdd(e) => e.nonce = nonce,
+                    PrecompileEvent::Bn254ScalarSub(e) => e.nonce = nonce,
+                    PrecompileEvent::Bn254ScalarMul(e) => e.nonce = nonce,
+                    PrecompileEvent::Bn254ScalarDiv(e) => e.nonce = nonce,
+                    PrecompileEvent::Bn254ScalarMac(e) => e.nonce = nonce,
+                    PrecompileEvent::EdDecompress(e) => e.nonce = nonce,
+                    PrecompileEvent::MemCopy(e) => e.nonce = nonce,
+                    PrecompileEvent::Bls12381G2Decompress(e) => e.nonce = nonce,
+                    PrecompileEvent::Secp256k1Compress(e) => e.nonce = nonce,
+                    PrecompileEvent::Bn254Compress(e) => e.nonce = nonce,
+                    PrecompileEvent::Secp256r1Compress(e) => e.nonce = nonce,
+                    PrecompileEvent::Bls12381Compress(e) => e.nonce = nonce,
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    /// Writes this record to `w` under `mode`, for checkpointing between shards during a long
+    /// execution instead of holding every shard's record in memory at once. If `mode.compact` is
+    /// set, `memory_local_events` is pulled out and varint-encoded separately (see
+    /// [`Self::compact_encode_memory_local_events`]) before the rest of the record is
+    /// `bincode`-encoded as usual; the two pieces are length-prefixed and concatenated. The result
+    /// is then piped through `compressor` if `mode.compressed` is set.
+    ///
+    /// Takes `&mut self` rather than `&self`: this is a checkpoint operation meant to cut *peak*
+    /// memory for shards with millions of events, so it must not clone the whole record (every
+    /// precompile-event map, every ALU/CPU/byte-lookup vector) just to strip one field out.
+    /// Instead `memory_local_events` is `mem::take`n out (an O(1) move), the rest is serialized by
+    /// reference, and the field is put back before returning.
+    pub fn write_compressed<W: Write>(
+        &mut self,
+        mode: RecordSerializationMode,
+        compressor: &dyn RecordCompressor,
+        mut w: W,
+    ) -> bincode::Result<()> {
+        let bytes = if mode.compact {
+            let memory_local_events = take(&mut self.memory_local_events);
+            let prefix = Self::compact_encode_memory_local_events(&memory_local_events);
+            let rest = bincode::serialize(&*self);
+            self.memory_local_events = memory_local_events;
+            let rest = rest?;
+
+            let mut bytes = Vec::new();
+            write_varint_u32(prefix.len() as u32, &mut bytes);
+            bytes.extend_from_slice(&prefix);
+            bytes.extend_from_slice(&rest);
+            bytes
+        } else {
+            bincode::serialize(self)?
+        };
+        let bytes = if mode.compressed {
+            compressor.compress(&bytes)
+        } else {
+            bytes
+        };
+        w.write_all(&bytes)
+            .map_err(|err| Box::new(bincode::ErrorKind::Io(err)))?;
+        Ok(())
+    }
+
+    /// Inverse of [`Self::write_compressed`]: reads all of `r`, undoes `compressor` if
+    /// `mode.compressed` is set, then -- if `mode.compact` is set -- splits the varint-encoded
+    /// `memory_local_events` prefix back off before `bincode`-decoding the rest.
+    pub fn read_compressed<R: Read>(
+        mode: RecordSerializationMode,
+        compressor: &dyn RecordCompressor,
+        mut r: R,
+    ) -> bincode::Result<Self> {
+        let mut bytes = Vec::new();
+        r.read_to_end(&mut bytes)
+            .map_err(|err| Box::new(bincode::ErrorKind::Io(err)))?;
+        let bytes = if mode.compressed {
+            compressor.decompress(&bytes)
+        } else {
+            bytes
+        };
+
+        if mode.compact {
+            let mut pos = 0;
+            let prefix_len = read_varint_u32(&bytes, &mut pos) as usize;
+            let memory_local_events =
+                Self::compact_decode_memory_local_events(&bytes[pos..pos + prefix_len]);
+            pos += prefix_len;
+            let mut record: Self = bincode::deserialize(&bytes[pos..])?;
+            record.memory_local_events = memory_local_events;
+            Ok(record)
+        } else {
+            bincode::deserialize(&bytes)
+        }
+    }
+
+    /// Columnar/delta pre-transform for `MemoryLocalEvent`: all `addr`s are written as one
+    /// contiguous run, delta-encoded against the previous event's `addr` (zigzag-varint, so it
+    /// handles the run going either up or down) instead of each event's `addr` standing alone at
+    /// a fixed 4-byte width; the `(initial_mem_access, final_mem_access)` pairs follow as a
+    /// second, separate run. Addresses touched within one shard cluster tightly (stack/heap
+    /// regions, buffer reads/writes), so consecutive deltas are usually small -- this gives a
+    /// compressor downstream (see `mode.compressed` in [`Self::write_compressed`]) long runs of
+    /// near-identical bytes instead of the original interleaved, full-width layout.
+    fn compact_encode_memory_local_events(events: &[MemoryLocalEvent]) -> Vec<u8> {
+        let mut out = Vec::new();
+        write_varint_u32(events.len() as u32, &mut out);
+        let mut prev_addr: i64 = 0;
+        for event in events {
+            let delta = event.addr as i64 - prev_addr;
+            write_varint_u64(zigzag_encode(delta), &mut out);
+            prev_addr = event.addr as i64;
+        }
+        let accesses: Vec<_> = events
+            .iter()
+            .map(|event| (event.initial_mem_access, event.final_mem_access))
+            .collect();
+        out.extend_from_slice(&bincode::serialize(&accesses).expect("in-memory bincode"));
+        out
+    }
+
+    /// Inverse of [`Self::compact_encode_memory_local_events`].
+    fn compact_decode_memory_local_events(bytes: &[u8]) -> Vec<MemoryLocalEvent> {
+        let mut pos = 0;
+        let len = read_varint_u32(bytes, &mut pos) as usize;
+        let mut prev_addr: i64 = 0;
+        let addrs: Vec<u32> = (0..len)
+            .map(|_| {
+                let delta = zigzag_decode(read_varint_u64(bytes, &mut pos));
+                prev_addr += delta;
+                prev_addr as u32
+            })
+            .collect();
+        let accesses: Vec<(MemoryRecordEnum, MemoryRecordEnum)> =
+            bincode::deserialize(&bytes[pos..]).expect("in-memory bincode");
+        addrs
+            .into_iter()
+            .zip(accesses)
+            .map(
+                |(addr, (initial_mem_access, final_mem_access))| MemoryLocalEvent {
+                    addr,
+                    initial_mem_access,
+                    final_mem_access,
+                },
+            )
+            .collect()
+    }
+
+    /// Pushes a single event. `mul_events` is a [`ChunkedVec`], so this never copies the events
+    /// pushed so far the way a growing `Vec` eventually would.
     pub fn add_mul_event(&mut self, mul_event: AluEvent) {
         self.mul_events.push(mul_event);
     }
@@ -676,6 +965,34 @@ impl ExecutionRecord {
     }
 
     pub fn add_alu_events(&mut self, alu_events: &HashMap<Opcode, Vec<AluEvent>>) {
+        // Reserve each destination vector by the exact number of events headed into it -- not a
+        // single hint applied across all of them, which would over-reserve vectors fed by
+        // opcodes absent from this batch while possibly under-sizing the one that isn't. Since
+        // these vectors are `ChunkedVec`s, reserving tops up full chunks rather than growing one
+        // contiguous allocation, so there's no risk of this copying anything already pushed.
+        let group_len = |opcodes: &[Opcode]| -> usize {
+            opcodes
+                .iter()
+                .filter_map(|opcode| alu_events.get(opcode))
+                .map(Vec::len)
+                .sum()
+        };
+        self.add_events.reserve(group_len(&[Opcode::ADD]));
+        self.mul_events.reserve(group_len(&[
+            Opcode::MUL,
+            Opcode::MULH,
+            Opcode::MULHU,
+            Opcode::MULHSU,
+        ]));
+        self.sub_events.reserve(group_len(&[Opcode::SUB]));
+        self.bitwise_events
+            .reserve(group_len(&[Opcode::XOR, Opcode::OR, Opcode::AND]));
+        self.shift_left_events.reserve(group_len(&[Opcode::SLL]));
+        self.shift_right_events
+            .reserve(group_len(&[Opcode::SRL, Opcode::SRA]));
+        self.lt_events
+            .reserve(group_len(&[Opcode::SLT, Opcode::SLTU]));
+
         let keys = alu_events.keys().sorted();
         for opcode in keys {
             match opcode {
@@ -708,9 +1025,111 @@ impl ExecutionRecord {
             }
         }
     }
+
+    /// Records a precompile-emitted event under the syscall that produced it.
+    pub fn add_precompile_event(&mut self, syscall_code: SyscallCode, event: PrecompileEvent) {
+        self.precompile_events
+            .entry(syscall_code)
+            .or_default()
+            .push(event);
+    }
+
+    /// Returns `code`'s precompile events, if any were recorded for this shard. Chips that own a
+    /// single `SyscallCode` (e.g. `Bn254ScalarMacChip`) can use this directly instead of a
+    /// bespoke accessor; chips that serve several `SyscallCode`s with one event shape (e.g.
+    /// `Bn254ScalarOpChip`, one code per `FieldOperation`) still need their own `syscall_code`
+    /// mapping first.
+    pub fn get_precompile_events(&self, code: SyscallCode) -> &[PrecompileEvent] {
+        self.precompile_events
+            .get(&code)
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+    }
+
+    /// Records the first-read/last-write pair for an address touched during this shard, so the
+    /// memory argument can reconcile it locally instead of through the global initialize/finalize
+    /// tables.
+    pub fn add_memory_local_event(&mut self, event: MemoryLocalEvent) {
+        self.memory_local_events.push(event);
+    }
+
+    /// Notes an `a`/`b`/`c`/`memory` access to `addr` during the shard currently being executed.
+    /// The first call for a given address seeds `initial_mem_access`; every later call for the
+    /// same address overwrites `final_mem_access`, so by the time the shard ends each address
+    /// maps to exactly its first and last touch.
+    pub fn update_local_memory_access(&mut self, addr: u32, record: MemoryRecordEnum) {
+        let shard = match record {
+            MemoryRecordEnum::Read(r) => r.shard,
+            MemoryRecordEnum::Write(w) => w.shard,
+        };
+        self.local_memory_access
+            .entry((shard, addr))
+            .and_modify(|event| event.final_mem_access = record)
+            .or_insert(MemoryLocalEvent {
+                addr,
+                initial_mem_access: record,
+                final_mem_access: record,
+            });
+    }
+
+    /// Calls [`Self::update_local_memory_access`] for every word of a contiguous read starting at
+    /// `base_ptr`, as returned by `SyscallContext::mr_slice`. Precompile chips read/write whole
+    /// buffers rather than single words, so this saves every call site from re-deriving each
+    /// word's address from the slice it already has.
+    pub fn update_local_memory_access_reads(
+        &mut self,
+        base_ptr: u32,
+        records: &[MemoryReadRecord],
+    ) {
+        for (i, record) in records.iter().enumerate() {
+            self.update_local_memory_access(base_ptr + 4 * i as u32, record.clone().into());
+        }
+    }
+
+    /// Write counterpart to [`Self::update_local_memory_access_reads`], for `SyscallContext::mw_slice`.
+    pub fn update_local_memory_access_writes(
+        &mut self,
+        base_ptr: u32,
+        records: &[MemoryWriteRecord],
+    ) {
+        for (i, record) in records.iter().enumerate() {
+            self.update_local_memory_access(base_ptr + 4 * i as u32, record.clone().into());
+        }
+    }
+
+    /// Drains the in-progress `local_memory_access` map into `memory_local_events`. Called once,
+    /// at the end of the whole run (see [`MachineRecord::shard`]), not at each shard boundary.
+    pub fn finalize_local_memory_access(&mut self) {
+        self.memory_local_events
+            .extend(take(&mut self.local_memory_access).into_values());
+    }
+}
+
+impl MemCopyWidth for U8 {
+    const SYSCALL_CODE: SyscallCode = SyscallCode::MEMCOPY;
+
+    fn wrap_event(event: MemCopyEvent<Self>) -> PrecompileEvent {
+        PrecompileEvent::MemCopy(event)
+    }
+
+    fn memcopy_events(record: &ExecutionRecord) -> Vec<&MemCopyEvent<Self>> {
+        record
+            .get_precompile_events(Self::SYSCALL_CODE)
+            .iter()
+            .map(|event| match event {
+                PrecompileEvent::MemCopy(event) => event,
+                _ => unreachable!("MEMCOPY syscall code maps only to MemCopy events"),
+            })
+            .collect()
+    }
 }
 
 impl ByteRecord for ExecutionRecord {
+    /// `byte_lookups` is keyed through two nested `BTreeMap`s, not a `Vec`/[`ChunkedVec`]: each
+    /// distinct `(shard, event)` pair is recorded once and then only its count is incremented, so
+    /// there's no growing element backing store here for chunked/arena storage to apply to in the
+    /// first place -- a `BTreeMap`'s own node-based growth already doesn't bulk-copy its elements
+    /// the way a reallocating `Vec` would.
     fn add_byte_lookup_event(&mut self, blu_event: ByteLookupEvent) {
         *self
             .byte_lookups
@@ -728,3 +1147,177 @@ pub struct MemoryAccessRecord {
     pub c: Option<MemoryRecordEnum>,
     pub memory: Option<MemoryRecordEnum>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::{MemoryReadRecord, MemoryWriteRecord};
+
+    /// A no-op [`RecordCompressor`] so serialization tests don't need a real codec dependency.
+    struct IdentityCompressor;
+
+    impl RecordCompressor for IdentityCompressor {
+        fn compress(&self, bytes: &[u8]) -> Vec<u8> {
+            bytes.to_vec()
+        }
+
+        fn decompress(&self, bytes: &[u8]) -> Vec<u8> {
+            bytes.to_vec()
+        }
+    }
+
+    fn sample_memory_local_events() -> Vec<MemoryLocalEvent> {
+        let read = MemoryRecordEnum::Read(MemoryReadRecord {
+            value: 42,
+            shard: 1,
+            timestamp: 10,
+            prev_shard: 1,
+            prev_timestamp: 5,
+        });
+        let write = MemoryRecordEnum::Write(MemoryWriteRecord {
+            value: 99,
+            shard: 1,
+            timestamp: 20,
+            prev_value: 42,
+            prev_shard: 1,
+            prev_timestamp: 10,
+        });
+        vec![
+            MemoryLocalEvent {
+                addr: 100,
+                initial_mem_access: read,
+                final_mem_access: write,
+            },
+            // addr goes down from the previous event, exercising a negative delta.
+            MemoryLocalEvent {
+                addr: 96,
+                initial_mem_access: write,
+                final_mem_access: write,
+            },
+            // same addr as the previous event, exercising a zero delta.
+            MemoryLocalEvent {
+                addr: 96,
+                initial_mem_access: read,
+                final_mem_access: read,
+            },
+        ]
+    }
+
+    fn assert_memory_local_events_eq(expected: &[MemoryLocalEvent], actual: &[MemoryLocalEvent]) {
+        assert_eq!(expected.len(), actual.len());
+        for (a, b) in expected.iter().zip(actual) {
+            assert_eq!(a.addr, b.addr);
+            // `MemoryRecordEnum` doesn't implement `PartialEq`, so compare via `Debug` instead.
+            assert_eq!(
+                format!("{:?}", a.initial_mem_access),
+                format!("{:?}", b.initial_mem_access)
+            );
+            assert_eq!(
+                format!("{:?}", a.final_mem_access),
+                format!("{:?}", b.final_mem_access)
+            );
+        }
+    }
+
+    #[test]
+    fn varint_u32_roundtrip() {
+        for value in [0u32, 1, 127, 128, 16384, u32::MAX] {
+            let mut bytes = Vec::new();
+            write_varint_u32(value, &mut bytes);
+            let mut pos = 0;
+            assert_eq!(read_varint_u32(&bytes, &mut pos), value);
+            assert_eq!(pos, bytes.len());
+        }
+    }
+
+    #[test]
+    fn varint_u64_roundtrip() {
+        for value in [0u64, 1, 127, 128, u32::MAX as u64, u64::MAX] {
+            let mut bytes = Vec::new();
+            write_varint_u64(value, &mut bytes);
+            let mut pos = 0;
+            assert_eq!(read_varint_u64(&bytes, &mut pos), value);
+            assert_eq!(pos, bytes.len());
+        }
+    }
+
+    #[test]
+    fn zigzag_roundtrip() {
+        for delta in [
+            0i64,
+            1,
+            -1,
+            127,
+            -127,
+            i64::from(u32::MAX),
+            -i64::from(u32::MAX),
+        ] {
+            assert_eq!(zigzag_decode(zigzag_encode(delta)), delta);
+        }
+    }
+
+    #[test]
+    fn compact_memory_local_events_roundtrip() {
+        let events = sample_memory_local_events();
+        let encoded = ExecutionRecord::compact_encode_memory_local_events(&events);
+        let decoded = ExecutionRecord::compact_decode_memory_local_events(&encoded);
+        assert_memory_local_events_eq(&events, &decoded);
+    }
+
+    #[test]
+    fn compact_memory_local_events_roundtrip_empty() {
+        let encoded = ExecutionRecord::compact_encode_memory_local_events(&[]);
+        let decoded = ExecutionRecord::compact_decode_memory_local_events(&encoded);
+        assert!(decoded.is_empty());
+    }
+
+    #[test]
+    fn write_read_compressed_roundtrip_compact() {
+        let mut record = ExecutionRecord {
+            index: 7,
+            memory_local_events: sample_memory_local_events(),
+            ..Default::default()
+        };
+        let original_events = record.memory_local_events.clone();
+        let mode = RecordSerializationMode {
+            compact: true,
+            compressed: false,
+        };
+        let compressor = IdentityCompressor;
+
+        let mut bytes = Vec::new();
+        record
+            .write_compressed(mode, &compressor, &mut bytes)
+            .unwrap();
+        // `write_compressed` must put `memory_local_events` back on `record` itself.
+        assert_memory_local_events_eq(&original_events, &record.memory_local_events);
+
+        let decoded =
+            ExecutionRecord::read_compressed(mode, &compressor, bytes.as_slice()).unwrap();
+        assert_eq!(decoded.index, record.index);
+        assert_memory_local_events_eq(&original_events, &decoded.memory_local_events);
+    }
+
+    #[test]
+    fn write_read_compressed_roundtrip_plain_compressed() {
+        let mut record = ExecutionRecord {
+            index: 3,
+            memory_local_events: sample_memory_local_events(),
+            ..Default::default()
+        };
+        let mode = RecordSerializationMode {
+            compact: false,
+            compressed: true,
+        };
+        let compressor = IdentityCompressor;
+
+        let mut bytes = Vec::new();
+        record
+            .write_compressed(mode, &compressor, &mut bytes)
+            .unwrap();
+        let decoded =
+            ExecutionRecord::read_compressed(mode, &compressor, bytes.as_slice()).unwrap();
+        assert_eq!(decoded.index, record.index);
+        assert_memory_local_events_eq(&record.memory_local_events, &decoded.memory_local_events);
+    }
+}
@@ -0,0 +1,663 @@
+//! BN254 scalar-field (`Fr`) arithmetic precompiles: `add`/`sub`/`mul`/`div` via
+//! [`Bn254ScalarOpChip`] and a fused multiply-accumulate (`a*b + c mod r`) via
+//! [`Bn254ScalarMacChip`]. The mac variant exists because folding a multiply and an add into one
+//! syscall roughly halves the cycle count of Poseidon-over-bn254 permutations, which is the
+//! dominant cost of in-circuit Merkle hashing over this field.
+
+use core::{
+    borrow::{Borrow, BorrowMut},
+    mem::size_of,
+};
+
+use hybrid_array::{typenum::Unsigned, Array};
+use num::{BigUint, Zero};
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::{AbstractField, PrimeField32};
+use p3_matrix::{dense::RowMajorMatrix, Matrix};
+use serde::{Deserialize, Serialize};
+use wp1_derive::AlignedBorrow;
+
+use crate::air::MachineAir;
+use crate::air::SP1AirBuilder;
+use crate::bytes::event::ByteRecord;
+use crate::bytes::ByteLookupEvent;
+use crate::memory::MemoryCols;
+use crate::memory::MemoryReadCols;
+use crate::memory::MemoryWriteCols;
+use crate::operations::field::field_op::FieldOpCols;
+use crate::operations::field::field_op::FieldOperation;
+use crate::operations::field::params::Limbs;
+use crate::operations::field::params::WORDS_FIELD_ELEMENT;
+use crate::operations::field::range::FieldRangeCols;
+use crate::runtime::ExecutionRecord;
+use crate::runtime::MemoryReadRecord;
+use crate::runtime::MemoryWriteRecord;
+use crate::runtime::PrecompileEvent;
+use crate::runtime::Program;
+use crate::runtime::SyscallCode;
+use crate::syscall::precompiles::SyscallContext;
+use crate::utils::bytes_to_words_le;
+use crate::utils::ec::field::FieldParameters;
+use crate::utils::ec::weierstrass::bn254::Bn254ScalarField;
+use crate::utils::limbs_from_access;
+use crate::utils::limbs_from_prev_access;
+use crate::utils::pad_vec_rows;
+
+/// A BN254 scalar-field (`Fr`) binary operation event: `result = p OP q mod r`, for whichever
+/// `OP` the owning [`Bn254ScalarOpChip`] is configured with. Mirrors [`FieldArithEvent`](
+/// crate::syscall::precompiles::field::op::FieldArithEvent), shared across Add/Sub/Mul/Div so
+/// that each operation doesn't need its own event type.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Bn254ScalarOpEvent {
+    pub shard: u32,
+    pub clk: u32,
+    /// This event's index within its shard's per-syscall vector, stamped by
+    /// [`ExecutionRecord::assign_nonces`](crate::runtime::ExecutionRecord::assign_nonces) once
+    /// the shard is finalized. Defaults to `0` so older serialized records still deserialize.
+    #[serde(default)]
+    pub nonce: u32,
+    pub p_ptr: u32,
+    #[serde(with = "crate::utils::array_serde::ArraySerde")]
+    pub p: Array<u32, WORDS_FIELD_ELEMENT<<Bn254ScalarField as FieldParameters>::NB_LIMBS>>,
+    pub q_ptr: u32,
+    #[serde(with = "crate::utils::array_serde::ArraySerde")]
+    pub q: Array<u32, WORDS_FIELD_ELEMENT<<Bn254ScalarField as FieldParameters>::NB_LIMBS>>,
+    #[serde(with = "crate::utils::array_serde::ArraySerde")]
+    pub p_memory_records:
+        Array<MemoryWriteRecord, WORDS_FIELD_ELEMENT<<Bn254ScalarField as FieldParameters>::NB_LIMBS>>,
+    #[serde(with = "crate::utils::array_serde::ArraySerde")]
+    pub q_memory_records:
+        Array<MemoryReadRecord, WORDS_FIELD_ELEMENT<<Bn254ScalarField as FieldParameters>::NB_LIMBS>>,
+}
+
+/// Columns for the BN254 scalar-field op precompile. One chip instance is configured per
+/// operation (see [`Bn254ScalarOpChip::new`]), so there is no opcode selector here, the same way
+/// `FieldOpChip<FP>` has none.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct Bn254ScalarOpCols<T> {
+    pub is_real: T,
+    /// This row's index within the shard's trace for this chip, constrained to match the
+    /// `nonce` sent to `receive_syscall` so no two rows can collide in the cross-table argument.
+    pub nonce: T,
+    pub shard: T,
+    pub clk: T,
+    pub p_ptr: T,
+    pub q_ptr: T,
+    pub p_access:
+        Array<MemoryWriteCols<T>, WORDS_FIELD_ELEMENT<<Bn254ScalarField as FieldParameters>::NB_LIMBS>>,
+    pub q_access:
+        Array<MemoryReadCols<T>, WORDS_FIELD_ELEMENT<<Bn254ScalarField as FieldParameters>::NB_LIMBS>>,
+    pub(crate) p_range: FieldRangeCols<T, Bn254ScalarField>,
+    pub(crate) p_op_q: FieldOpCols<T, <Bn254ScalarField as FieldParameters>::NB_LIMBS>,
+}
+
+/// Performs `result = p OP q mod Bn254ScalarField::modulus()`, aliasing-safe for `p == q`.
+/// Division is supported here (unlike the base-field `FieldOpChip`) since scalar-field inverses
+/// come up directly in ECDSA-style verification.
+pub fn create_bn254_scalar_op_event(
+    rt: &mut SyscallContext<'_>,
+    arg1: u32,
+    arg2: u32,
+    op: FieldOperation,
+) -> Bn254ScalarOpEvent {
+    let start_clk = rt.clk;
+    let p_ptr = arg1;
+    let q_ptr = arg2;
+    assert!(p_ptr % 4 == 0);
+    assert!(q_ptr % 4 == 0);
+
+    let words_len = WORDS_FIELD_ELEMENT::<<Bn254ScalarField as FieldParameters>::NB_LIMBS>::USIZE;
+
+    let (q_memory_records_vec, q_vec) = rt.mr_slice(q_ptr, words_len);
+    rt.record_mut()
+        .update_local_memory_access_reads(q_ptr, &q_memory_records_vec);
+    let q_memory_records = (&q_memory_records_vec[..]).try_into().unwrap();
+    let q: Array<u32, _> = (&q_vec[..]).try_into().unwrap();
+    let q_int = BigUint::from_slice(&q);
+
+    let p: Array<u32, _> = (&rt.slice_unsafe(p_ptr, words_len)[..]).try_into().unwrap();
+    let p_int = BigUint::from_slice(&p);
+
+    let modulus = Bn254ScalarField::modulus();
+    let result_int = match op {
+        FieldOperation::Add => (&p_int + &q_int) % &modulus,
+        FieldOperation::Sub => (&modulus + &p_int - &q_int) % &modulus,
+        FieldOperation::Mul => (&p_int * &q_int) % &modulus,
+        FieldOperation::Div => {
+            assert!(!q_int.is_zero(), "bn254 scalar-field division by zero");
+            let q_inv = q_int.modpow(&(&modulus - 2u32), &modulus);
+            (&p_int * &q_inv) % &modulus
+        }
+    };
+    let result_bytes = Bn254ScalarField::to_limbs(&result_int);
+    let result_words =
+        bytes_to_words_le::<WORDS_FIELD_ELEMENT<<Bn254ScalarField as FieldParameters>::NB_LIMBS>>(
+            &result_bytes,
+        );
+
+    // When we write to p, we want the clk to be incremented because p and q could be the same.
+    rt.clk += 1;
+    let p_memory_records_vec = rt.mw_slice(p_ptr, &result_words.into());
+    rt.record_mut()
+        .update_local_memory_access_writes(p_ptr, &p_memory_records_vec);
+    let p_memory_records = (&p_memory_records_vec[..]).try_into().unwrap();
+
+    Bn254ScalarOpEvent {
+        shard: rt.current_shard(),
+        clk: start_clk,
+        nonce: 0,
+        p_ptr,
+        p,
+        q_ptr,
+        q,
+        p_memory_records,
+        q_memory_records,
+    }
+}
+
+/// A configurable BN254 scalar-field precompile that proves `p = p OP q mod r` for whichever
+/// binary operation it is instantiated with, the same way `FieldOpChip<FP>` is instantiated once
+/// per operation over a base field.
+pub struct Bn254ScalarOpChip {
+    op: FieldOperation,
+}
+
+impl Bn254ScalarOpChip {
+    pub fn new(op: FieldOperation) -> Self {
+        Self { op }
+    }
+}
+
+impl<F: PrimeField32> MachineAir<F> for Bn254ScalarOpChip {
+    type Record = ExecutionRecord;
+    type Program = Program;
+
+    fn name(&self) -> String {
+        format!("Bn254Scalar{:?}", self.op)
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        output: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let events = Self::events_for(input, self.op);
+        let mut rows = Vec::new();
+        let mut new_byte_lookup_events = Vec::new();
+
+        let words_len =
+            WORDS_FIELD_ELEMENT::<<Bn254ScalarField as FieldParameters>::NB_LIMBS>::USIZE;
+
+        for event in events.into_iter() {
+            let mut row = vec![F::zero(); size_of::<Bn254ScalarOpCols<u8>>()];
+            let cols: &mut Bn254ScalarOpCols<F> = row.as_mut_slice().borrow_mut();
+
+            cols.is_real = F::one();
+            cols.nonce = F::from_canonical_u32(event.nonce);
+            cols.shard = F::from_canonical_u32(event.shard);
+            cols.clk = F::from_canonical_u32(event.clk);
+            cols.p_ptr = F::from_canonical_u32(event.p_ptr);
+            cols.q_ptr = F::from_canonical_u32(event.q_ptr);
+
+            let p_int = BigUint::from_slice(&event.p);
+            let q_int = BigUint::from_slice(&event.q);
+            let result = cols.p_op_q.populate::<Bn254ScalarField>(&p_int, &q_int, self.op);
+            cols.p_range
+                .populate(&mut new_byte_lookup_events, event.shard, &result);
+
+            for i in 0..words_len {
+                cols.q_access[i].populate(event.q_memory_records[i], &mut new_byte_lookup_events);
+            }
+            for i in 0..words_len {
+                cols.p_access[i].populate(event.p_memory_records[i], &mut new_byte_lookup_events);
+            }
+
+            rows.push(row);
+        }
+        output.add_byte_lookup_events(new_byte_lookup_events);
+
+        pad_vec_rows(&mut rows, || {
+            let mut row = vec![F::zero(); size_of::<Bn254ScalarOpCols<u8>>()];
+            let cols: &mut Bn254ScalarOpCols<F> = row.as_mut_slice().borrow_mut();
+            let zero = BigUint::zero();
+            cols.p_op_q.populate::<Bn254ScalarField>(&zero, &zero, self.op);
+            row
+        });
+
+        RowMajorMatrix::new(
+            rows.into_iter().flatten().collect::<Vec<_>>(),
+            size_of::<Bn254ScalarOpCols<u8>>(),
+        )
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        !Self::events_for(shard, self.op).is_empty()
+    }
+}
+
+impl Bn254ScalarOpChip {
+    fn events_for(record: &ExecutionRecord, op: FieldOperation) -> Vec<&Bn254ScalarOpEvent> {
+        record
+            .get_precompile_events(Self::syscall_code(op))
+            .iter()
+            .map(|event| match event {
+                PrecompileEvent::Bn254ScalarAdd(event)
+                | PrecompileEvent::Bn254ScalarSub(event)
+                | PrecompileEvent::Bn254ScalarMul(event)
+                | PrecompileEvent::Bn254ScalarDiv(event) => event,
+                _ => unreachable!(
+                    "bn254 scalar op syscalls map only to Bn254Scalar{{Add,Sub,Mul,Div}} events"
+                ),
+            })
+            .collect()
+    }
+
+    fn syscall_code(op: FieldOperation) -> SyscallCode {
+        match op {
+            FieldOperation::Add => SyscallCode::BN254_SCALAR_ADD,
+            FieldOperation::Sub => SyscallCode::BN254_SCALAR_SUB,
+            FieldOperation::Mul => SyscallCode::BN254_SCALAR_MUL,
+            FieldOperation::Div => SyscallCode::BN254_SCALAR_DIV,
+        }
+    }
+}
+
+impl<F> BaseAir<F> for Bn254ScalarOpChip {
+    fn width(&self) -> usize {
+        size_of::<Bn254ScalarOpCols<u8>>()
+    }
+}
+
+impl<AB: SP1AirBuilder> Air<AB> for Bn254ScalarOpChip {
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let row: &Bn254ScalarOpCols<AB::Var> = main.row_slice(0).borrow();
+        let next: &Bn254ScalarOpCols<AB::Var> = main.row_slice(1).borrow();
+
+        // The nonce of the first row is 0, and nonces increment by 1 for each subsequent real row.
+        builder.when_first_row().assert_zero(row.nonce);
+        builder
+            .when_transition()
+            .when(next.is_real)
+            .assert_eq(row.nonce + AB::F::one(), next.nonce);
+
+        let p: Limbs<_, <Bn254ScalarField as FieldParameters>::NB_LIMBS> =
+            limbs_from_prev_access(&row.p_access);
+        let q: Limbs<_, <Bn254ScalarField as FieldParameters>::NB_LIMBS> =
+            limbs_from_prev_access(&row.q_access);
+
+        row.p_op_q
+            .eval::<AB, Bn254ScalarField, _, _>(builder, &p, &q, self.op);
+        row.p_range
+            .eval(builder, &row.p_op_q.result, row.shard, row.is_real);
+
+        let p_limbs: Limbs<_, <Bn254ScalarField as FieldParameters>::NB_LIMBS> =
+            limbs_from_access(&row.p_access);
+        builder
+            .when(row.is_real)
+            .assert_all_eq(row.p_op_q.result.clone(), p_limbs);
+
+        let words_len =
+            WORDS_FIELD_ELEMENT::<<Bn254ScalarField as FieldParameters>::NB_LIMBS>::USIZE;
+        for i in 0..words_len {
+            builder.eval_memory_access(
+                row.shard,
+                row.clk,
+                row.q_ptr.into() + AB::F::from_canonical_u32((i as u32) * 4),
+                &row.q_access[i],
+                row.is_real,
+            );
+        }
+        for i in 0..words_len {
+            builder.eval_memory_access(
+                row.shard,
+                row.clk + AB::F::one(),
+                row.p_ptr.into() + AB::F::from_canonical_u32((i as u32) * 4),
+                &row.p_access[i],
+                row.is_real,
+            );
+        }
+
+        let syscall_id_fe = AB::F::from_canonical_u32(Self::syscall_code(self.op).syscall_id());
+        builder.receive_syscall(
+            row.shard,
+            row.clk,
+            row.nonce,
+            syscall_id_fe,
+            row.p_ptr,
+            row.q_ptr,
+            row.is_real,
+        );
+    }
+}
+
+/// Columns for the fused BN254 scalar-field multiply-accumulate precompile: `a*b + c mod r`.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct Bn254ScalarMacCols<T> {
+    pub is_real: T,
+    /// This row's index within the shard's trace for this chip, constrained to match the
+    /// `nonce` sent to `receive_syscall` so no two rows can collide in the cross-table argument.
+    pub nonce: T,
+    pub shard: T,
+    pub clk: T,
+    pub arg1_ptr: T,
+    pub arg2_ptr: T,
+    pub arg3_ptr: T,
+    pub arg1_access:
+        Array<MemoryWriteCols<T>, WORDS_FIELD_ELEMENT<<Bn254ScalarField as FieldParameters>::NB_LIMBS>>,
+    pub arg2_access:
+        Array<MemoryReadCols<T>, WORDS_FIELD_ELEMENT<<Bn254ScalarField as FieldParameters>::NB_LIMBS>>,
+    pub arg3_access:
+        Array<MemoryReadCols<T>, WORDS_FIELD_ELEMENT<<Bn254ScalarField as FieldParameters>::NB_LIMBS>>,
+    pub(crate) a_mul_b: FieldOpCols<T, <Bn254ScalarField as FieldParameters>::NB_LIMBS>,
+    pub(crate) ab_plus_c: FieldOpCols<T, <Bn254ScalarField as FieldParameters>::NB_LIMBS>,
+    pub(crate) range: FieldRangeCols<T, Bn254ScalarField>,
+}
+
+/// `result = a*b + c mod r`, written back to `arg1`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Bn254ScalarMacEvent {
+    pub shard: u32,
+    pub clk: u32,
+    /// This event's index within its shard's per-syscall vector, stamped by
+    /// [`ExecutionRecord::assign_nonces`](crate::runtime::ExecutionRecord::assign_nonces) once
+    /// the shard is finalized. Defaults to `0` so older serialized records still deserialize.
+    #[serde(default)]
+    pub nonce: u32,
+    pub arg1_ptr: u32,
+    #[serde(with = "crate::utils::array_serde::ArraySerde")]
+    pub c: Array<u32, WORDS_FIELD_ELEMENT<<Bn254ScalarField as FieldParameters>::NB_LIMBS>>,
+    pub arg2_ptr: u32,
+    #[serde(with = "crate::utils::array_serde::ArraySerde")]
+    pub a: Array<u32, WORDS_FIELD_ELEMENT<<Bn254ScalarField as FieldParameters>::NB_LIMBS>>,
+    pub arg3_ptr: u32,
+    #[serde(with = "crate::utils::array_serde::ArraySerde")]
+    pub b: Array<u32, WORDS_FIELD_ELEMENT<<Bn254ScalarField as FieldParameters>::NB_LIMBS>>,
+    #[serde(with = "crate::utils::array_serde::ArraySerde")]
+    pub arg1_memory_records:
+        Array<MemoryWriteRecord, WORDS_FIELD_ELEMENT<<Bn254ScalarField as FieldParameters>::NB_LIMBS>>,
+    #[serde(with = "crate::utils::array_serde::ArraySerde")]
+    pub arg2_memory_records:
+        Array<MemoryReadRecord, WORDS_FIELD_ELEMENT<<Bn254ScalarField as FieldParameters>::NB_LIMBS>>,
+    #[serde(with = "crate::utils::array_serde::ArraySerde")]
+    pub arg3_memory_records:
+        Array<MemoryReadRecord, WORDS_FIELD_ELEMENT<<Bn254ScalarField as FieldParameters>::NB_LIMBS>>,
+}
+
+#[derive(Default)]
+pub struct Bn254ScalarMacChip;
+
+impl Bn254ScalarMacChip {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn events(record: &ExecutionRecord) -> Vec<&Bn254ScalarMacEvent> {
+        record
+            .get_precompile_events(SyscallCode::BN254_SCALAR_MAC)
+            .iter()
+            .map(|event| match event {
+                PrecompileEvent::Bn254ScalarMac(event) => event,
+                _ => unreachable!("BN254_SCALAR_MAC syscall code maps only to Bn254ScalarMac events"),
+            })
+            .collect()
+    }
+}
+
+/// Reads `a = *arg2`, `b = *arg3`, `c = *arg1`, writes `a*b + c mod r` back to `arg1`. Wired into
+/// the syscall dispatch table the same way `create_fp_mac_event` is: the three-pointer calling
+/// convention is resolved by the dispatcher before reaching this chip.
+pub fn create_bn254_scalar_mac_event(
+    rt: &mut SyscallContext<'_>,
+    arg1: u32,
+    arg2: u32,
+    arg3: u32,
+) -> Bn254ScalarMacEvent {
+    let start_clk = rt.clk;
+    let arg1_ptr = arg1;
+    let arg2_ptr = arg2;
+    let arg3_ptr = arg3;
+    assert!(arg1_ptr % 4 == 0);
+    assert!(arg2_ptr % 4 == 0);
+    assert!(arg3_ptr % 4 == 0);
+
+    let words_len = WORDS_FIELD_ELEMENT::<<Bn254ScalarField as FieldParameters>::NB_LIMBS>::USIZE;
+
+    let (arg2_memory_records_vec, a_vec) = rt.mr_slice(arg2_ptr, words_len);
+    rt.record_mut()
+        .update_local_memory_access_reads(arg2_ptr, &arg2_memory_records_vec);
+    let arg2_memory_records = (&arg2_memory_records_vec[..]).try_into().unwrap();
+    let a: Array<u32, _> = (&a_vec[..]).try_into().unwrap();
+    let a_int = BigUint::from_slice(&a);
+
+    let (arg3_memory_records_vec, b_vec) = rt.mr_slice(arg3_ptr, words_len);
+    rt.record_mut()
+        .update_local_memory_access_reads(arg3_ptr, &arg3_memory_records_vec);
+    let arg3_memory_records = (&arg3_memory_records_vec[..]).try_into().unwrap();
+    let b: Array<u32, _> = (&b_vec[..]).try_into().unwrap();
+    let b_int = BigUint::from_slice(&b);
+
+    let c: Array<u32, _> = (&rt.slice_unsafe(arg1_ptr, words_len)[..]).try_into().unwrap();
+    let c_int = BigUint::from_slice(&c);
+
+    let result_int = (c_int + (&a_int * &b_int)) % Bn254ScalarField::modulus();
+    let result_bytes = Bn254ScalarField::to_limbs(&result_int);
+    let result_words =
+        bytes_to_words_le::<WORDS_FIELD_ELEMENT<<Bn254ScalarField as FieldParameters>::NB_LIMBS>>(
+            &result_bytes,
+        );
+
+    // When we write to arg1, we want the clk to be incremented because arg1 could alias arg2/arg3.
+    rt.clk += 1;
+    let arg1_memory_records_vec = rt.mw_slice(arg1_ptr, &result_words.into());
+    rt.record_mut()
+        .update_local_memory_access_writes(arg1_ptr, &arg1_memory_records_vec);
+    let arg1_memory_records = (&arg1_memory_records_vec[..]).try_into().unwrap();
+
+    Bn254ScalarMacEvent {
+        shard: rt.current_shard(),
+        clk: start_clk,
+        nonce: 0,
+        arg1_ptr,
+        c,
+        arg2_ptr,
+        a,
+        arg3_ptr,
+        b,
+        arg1_memory_records,
+        arg2_memory_records,
+        arg3_memory_records,
+    }
+}
+
+impl<F: PrimeField32> MachineAir<F> for Bn254ScalarMacChip {
+    type Record = ExecutionRecord;
+    type Program = Program;
+
+    fn name(&self) -> String {
+        "Bn254ScalarMac".to_string()
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        output: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let mut rows = Vec::new();
+        let mut new_byte_lookup_events = Vec::new();
+
+        let words_len =
+            WORDS_FIELD_ELEMENT::<<Bn254ScalarField as FieldParameters>::NB_LIMBS>::USIZE;
+
+        for event in Self::events(input).into_iter() {
+            let mut row = vec![F::zero(); size_of::<Bn254ScalarMacCols<u8>>()];
+            let cols: &mut Bn254ScalarMacCols<F> = row.as_mut_slice().borrow_mut();
+
+            cols.is_real = F::one();
+            cols.nonce = F::from_canonical_u32(event.nonce);
+            cols.shard = F::from_canonical_u32(event.shard);
+            cols.clk = F::from_canonical_u32(event.clk);
+            cols.arg1_ptr = F::from_canonical_u32(event.arg1_ptr);
+            cols.arg2_ptr = F::from_canonical_u32(event.arg2_ptr);
+            cols.arg3_ptr = F::from_canonical_u32(event.arg3_ptr);
+
+            let c_int = BigUint::from_slice(&event.c);
+            let a_int = BigUint::from_slice(&event.a);
+            let b_int = BigUint::from_slice(&event.b);
+
+            let ab = cols
+                .a_mul_b
+                .populate::<Bn254ScalarField>(&a_int, &b_int, FieldOperation::Mul);
+            let result = cols
+                .ab_plus_c
+                .populate::<Bn254ScalarField>(&ab, &c_int, FieldOperation::Add);
+            cols.range
+                .populate(&mut new_byte_lookup_events, event.shard, &result);
+
+            for i in 0..words_len {
+                cols.arg2_access[i]
+                    .populate(event.arg2_memory_records[i], &mut new_byte_lookup_events);
+            }
+            for i in 0..words_len {
+                cols.arg3_access[i]
+                    .populate(event.arg3_memory_records[i], &mut new_byte_lookup_events);
+            }
+            for i in 0..words_len {
+                cols.arg1_access[i]
+                    .populate(event.arg1_memory_records[i], &mut new_byte_lookup_events);
+            }
+
+            rows.push(row);
+        }
+        output.add_byte_lookup_events(new_byte_lookup_events);
+
+        pad_vec_rows(&mut rows, || {
+            let mut row = vec![F::zero(); size_of::<Bn254ScalarMacCols<u8>>()];
+            let cols: &mut Bn254ScalarMacCols<F> = row.as_mut_slice().borrow_mut();
+            let zero = BigUint::zero();
+            let ab = cols
+                .a_mul_b
+                .populate::<Bn254ScalarField>(&zero, &zero, FieldOperation::Mul);
+            cols.ab_plus_c
+                .populate::<Bn254ScalarField>(&ab, &zero, FieldOperation::Add);
+            row
+        });
+
+        RowMajorMatrix::new(
+            rows.into_iter().flatten().collect::<Vec<_>>(),
+            size_of::<Bn254ScalarMacCols<u8>>(),
+        )
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        !Self::events(shard).is_empty()
+    }
+}
+
+impl<F> BaseAir<F> for Bn254ScalarMacChip {
+    fn width(&self) -> usize {
+        size_of::<Bn254ScalarMacCols<u8>>()
+    }
+}
+
+impl<AB: SP1AirBuilder> Air<AB> for Bn254ScalarMacChip {
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let row: &Bn254ScalarMacCols<AB::Var> = main.row_slice(0).borrow();
+        let next: &Bn254ScalarMacCols<AB::Var> = main.row_slice(1).borrow();
+
+        // The nonce of the first row is 0, and nonces increment by 1 for each subsequent real row.
+        builder.when_first_row().assert_zero(row.nonce);
+        builder
+            .when_transition()
+            .when(next.is_real)
+            .assert_eq(row.nonce + AB::F::one(), next.nonce);
+
+        let a: Limbs<_, <Bn254ScalarField as FieldParameters>::NB_LIMBS> =
+            limbs_from_prev_access(&row.arg2_access);
+        let b: Limbs<_, <Bn254ScalarField as FieldParameters>::NB_LIMBS> =
+            limbs_from_prev_access(&row.arg3_access);
+        let c: Limbs<_, <Bn254ScalarField as FieldParameters>::NB_LIMBS> =
+            limbs_from_prev_access(&row.arg1_access);
+
+        row.a_mul_b
+            .eval::<AB, Bn254ScalarField, _, _>(builder, &a, &b, FieldOperation::Mul);
+        row.ab_plus_c.eval::<AB, Bn254ScalarField, _, _>(
+            builder,
+            &row.a_mul_b.result,
+            &c,
+            FieldOperation::Add,
+        );
+        row.range
+            .eval(builder, &row.ab_plus_c.result, row.shard, row.is_real);
+
+        let arg1_limbs: Limbs<_, <Bn254ScalarField as FieldParameters>::NB_LIMBS> =
+            limbs_from_access(&row.arg1_access);
+        builder
+            .when(row.is_real)
+            .assert_all_eq(row.ab_plus_c.result.clone(), arg1_limbs);
+
+        let words_len =
+            WORDS_FIELD_ELEMENT::<<Bn254ScalarField as FieldParameters>::NB_LIMBS>::USIZE;
+        for i in 0..words_len {
+            builder.eval_memory_access(
+                row.shard,
+                row.clk,
+                row.arg2_ptr.into() + AB::F::from_canonical_u32((i as u32) * 4),
+                &row.arg2_access[i],
+                row.is_real,
+            );
+        }
+        for i in 0..words_len {
+            builder.eval_memory_access(
+                row.shard,
+                row.clk,
+                row.arg3_ptr.into() + AB::F::from_canonical_u32((i as u32) * 4),
+                &row.arg3_access[i],
+                row.is_real,
+            );
+        }
+        for i in 0..words_len {
+            builder.eval_memory_access(
+                row.shard,
+                row.clk + AB::F::one(),
+                row.arg1_ptr.into() + AB::F::from_canonical_u32((i as u32) * 4),
+                &row.arg1_access[i],
+                row.is_real,
+            );
+        }
+
+        builder.receive_syscall(
+            row.shard,
+            row.clk,
+            row.nonce,
+            AB::F::from_canonical_u32(SyscallCode::BN254_SCALAR_MAC.syscall_id()),
+            row.arg1_ptr,
+            row.arg2_ptr,
+            row.is_real,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils;
+    use crate::utils::tests::{BN254_SCALAR_MAC_ELF, BN254_SCALAR_OP_ELF};
+    use crate::Program;
+
+    #[test]
+    fn test_bn254_scalar_op_simple() {
+        utils::setup_logger();
+        let program = Program::from(BN254_SCALAR_OP_ELF);
+        utils::run_test(program).unwrap();
+    }
+
+    #[test]
+    fn test_bn254_scalar_mac_simple() {
+        utils::setup_logger();
+        let program = Program::from(BN254_SCALAR_MAC_ELF);
+        utils::run_test(program).unwrap();
+    }
+}
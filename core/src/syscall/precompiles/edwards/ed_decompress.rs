@@ -32,6 +32,7 @@ use crate::operations::field::range::FieldRangeCols;
 use crate::runtime::ExecutionRecord;
 use crate::runtime::MemoryReadRecord;
 use crate::runtime::MemoryWriteRecord;
+use crate::runtime::PrecompileEvent;
 use crate::runtime::Program;
 use crate::runtime::Syscall;
 use crate::runtime::SyscallCode;
@@ -52,6 +53,11 @@ use crate::utils::words_to_bytes_le;
 pub struct EdDecompressEvent<U: LimbWidth = DEFAULT_NUM_LIMBS_T> {
     pub shard: u32,
     pub clk: u32,
+    /// This event's index within its shard's per-syscall vector, stamped by
+    /// [`ExecutionRecord::assign_nonces`](crate::runtime::ExecutionRecord::assign_nonces) once
+    /// the shard is finalized. Defaults to `0` so older serialized records still deserialize.
+    #[serde(default)]
+    pub nonce: u32,
     pub ptr: u32,
     pub sign: bool,
     #[serde(with = "crate::utils::array_serde::ArraySerde")]
@@ -75,6 +81,9 @@ pub struct EdDecompressEvent<U: LimbWidth = DEFAULT_NUM_LIMBS_T> {
 #[repr(C)]
 pub struct EdDecompressCols<T, P: FieldParameters> {
     pub is_real: T,
+    /// This row's index within the shard's trace for this chip, constrained to match the
+    /// `nonce` sent to `receive_syscall` so no two rows can collide in the cross-table argument.
+    pub nonce: T,
     pub shard: T,
     pub clk: T,
     pub ptr: T,
@@ -86,19 +95,33 @@ pub struct EdDecompressCols<T, P: FieldParameters> {
     pub(crate) u: FieldOpCols<T, P>,
     pub(crate) dyy: FieldOpCols<T, P>,
     pub(crate) v: FieldOpCols<T, P>,
+    /// `v`'s modular inverse, witnessed directly rather than recomputed from `v` by a `Div`
+    /// column: [`EdDecompressChip::generate_trace`] inverts every row's `v` in one batched pass
+    /// (see [`batch_invert_denominators`]) instead of paying for a full modular exponentiation
+    /// per row. Constrained against `v` by [`Self::v_inv_check`].
+    pub(crate) v_inv: Limbs<T, P::NB_LIMBS>,
+    /// `v * v_inv`, constrained to `1` so a malicious prover can't swap in an unrelated `v_inv`.
+    pub(crate) v_inv_check: FieldOpCols<T, P>,
     pub(crate) u_div_v: FieldOpCols<T, P>,
     pub(crate) x: FieldSqrtCols<T, P>,
     pub(crate) neg_x: FieldOpCols<T, P>,
 }
 
 impl<F: PrimeField32, P: FieldParameters> EdDecompressCols<F, P> {
+    /// `v_inv` is the modular inverse of `v = 1 + d*y^2`, precomputed by the caller -- batched
+    /// across every event in the shard by [`EdDecompressChip::generate_trace`] via
+    /// [`batch_invert_denominators`] -- rather than recomputed here per row.
     pub fn populate<E: EdwardsParameters<BaseField = P>>(
         &mut self,
-        event: &EdDecompressEvent,
+        nonce: usize,
+        event: &EdDecompressEvent<P::NB_LIMBS>,
         record: &mut ExecutionRecord,
+        sqrt: fn(&BigUint) -> BigUint,
+        v_inv: &BigUint,
     ) {
         let mut new_byte_lookup_events = Vec::new();
         self.is_real = F::from_bool(true);
+        self.nonce = F::from_canonical_usize(nonce);
         self.shard = F::from_canonical_u32(event.shard);
         self.clk = F::from_canonical_u32(event.clk);
         self.ptr = F::from_canonical_u32(event.ptr);
@@ -110,16 +133,25 @@ impl<F: PrimeField32, P: FieldParameters> EdDecompressCols<F, P> {
         }
 
         let y = &BigUint::from_bytes_le(&event.y_bytes);
-        self.populate_field_ops::<E>(&mut new_byte_lookup_events, event.shard, y);
+        self.populate_field_ops::<E>(&mut new_byte_lookup_events, event.shard, y, sqrt, v_inv);
 
         record.add_byte_lookup_events(new_byte_lookup_events);
     }
 
+    /// `sqrt` is the curve's modular square root routine, supplied by
+    /// [`EdDecompressChip`] rather than hardcoded here so a second curve (e.g. Ed448) can plug in
+    /// its own instead of this column gadget importing ed25519's directly.
+    ///
+    /// `v_inv` is the modular inverse of `v = 1 + d*y^2`, computed by the caller -- batched across
+    /// every event in the shard by [`EdDecompressChip::generate_trace`] -- rather than recomputed
+    /// here, so this never performs its own modular exponentiation.
     fn populate_field_ops<E: EdwardsParameters>(
         &mut self,
         blu_events: &mut Vec<ByteLookupEvent>,
         shard: u32,
         y: &BigUint,
+        sqrt: fn(&BigUint) -> BigUint,
+        v_inv: &BigUint,
     ) {
         let one = BigUint::one();
         self.y_range.populate(blu_events, shard, y);
@@ -135,15 +167,71 @@ impl<F: PrimeField32, P: FieldParameters> EdDecompressCols<F, P> {
         let v = self
             .v
             .populate(blu_events, shard, &one, &dyy, FieldOperation::Add);
+        self.v_inv = E::BaseField::to_limbs_field(v_inv);
+        self.v_inv_check
+            .populate(blu_events, shard, &v, v_inv, FieldOperation::Mul);
         let u_div_v = self
             .u_div_v
-            .populate(blu_events, shard, &u, &v, FieldOperation::Div);
-        let x = self.x.populate(blu_events, shard, &u_div_v, ed25519_sqrt);
+            .populate(blu_events, shard, &u, v_inv, FieldOperation::Mul);
+        let x = self.x.populate(blu_events, shard, &u_div_v, sqrt);
         self.neg_x
             .populate(blu_events, shard, &BigUint::zero(), &x, FieldOperation::Sub);
     }
 }
 
+/// `v`'s modular inverse via a single modular exponentiation, used only for the one-off padding
+/// row (not worth threading through the batch for a single value).
+fn mod_inverse<E: EdwardsParameters>(y: &BigUint, modulus: &BigUint) -> BigUint {
+    let yy = (y * y) % modulus;
+    let dyy = (&E::d_biguint() * &yy) % modulus;
+    let v = (BigUint::one() + dyy) % modulus;
+    v.modpow(&(modulus - 2u32), modulus)
+}
+
+/// Inverts every row's `v = 1 + d*y^2` in one pass using Montgomery's batch inversion trick:
+/// prefix products `p_0 = v_0`, `p_i = p_{i-1} * v_i`, a single inversion of `p_{n-1}`, then a
+/// backward walk recovering each `v_i^{-1} = p_{i-1} * acc` while updating `acc *= v_i` (with
+/// `p_{-1} = 1`). This turns `n` modular inversions (each an `O(log p)` exponentiation) into one
+/// inversion plus `~3n` multiplications -- the saving `EdDecompressChip::generate_trace` actually
+/// uses, unlike the per-row `Div` this replaced.
+fn batch_invert_denominators<E: EdwardsParameters>(
+    ys: &[BigUint],
+    modulus: &BigUint,
+) -> Vec<BigUint> {
+    let zero = BigUint::zero();
+    let one = BigUint::one();
+
+    let denominators: Vec<BigUint> = ys
+        .iter()
+        .map(|y| {
+            let yy = (y * y) % modulus;
+            let dyy = (&E::d_biguint() * &yy) % modulus;
+            (&one + dyy) % modulus
+        })
+        .collect();
+
+    let mut prefix_products = Vec::with_capacity(denominators.len());
+    let mut running_product = one.clone();
+    for value in &denominators {
+        if *value != zero {
+            running_product = (&running_product * value) % modulus;
+        }
+        prefix_products.push(running_product.clone());
+    }
+
+    let mut acc = running_product.modpow(&(modulus - 2u32), modulus);
+    let mut inverses = vec![zero.clone(); denominators.len()];
+    for i in (0..denominators.len()).rev() {
+        if denominators[i] == zero {
+            continue;
+        }
+        let prev_product = if i == 0 { &one } else { &prefix_products[i - 1] };
+        inverses[i] = (&acc * prev_product) % modulus;
+        acc = (&acc * &denominators[i]) % modulus;
+    }
+    inverses
+}
+
 impl<V: Copy, P: FieldParameters> EdDecompressCols<V, P> {
     pub fn eval<AB: SP1AirBuilder<Var = V>, E: EdwardsParameters<BaseField = P>>(
         &self,
@@ -189,11 +277,24 @@ impl<V: Copy, P: FieldParameters> EdDecompressCols<V, P> {
             self.shard,
             self.is_real,
         );
+        // `v * v_inv == 1`, so a prover can't witness an unrelated `v_inv` for the `Mul` below.
+        self.v_inv_check.eval(
+            builder,
+            &self.v.result,
+            &self.v_inv,
+            FieldOperation::Mul,
+            self.shard,
+            self.is_real,
+        );
+        builder.when(self.is_real).assert_all_eq(
+            self.v_inv_check.result,
+            E::BaseField::to_limbs_field::<AB::F>(&BigUint::one()),
+        );
         self.u_div_v.eval(
             builder,
             &self.u.result,
-            &self.v.result,
-            FieldOperation::Div,
+            &self.v_inv,
+            FieldOperation::Mul,
             self.shard,
             self.is_real,
         );
@@ -246,6 +347,7 @@ impl<V: Copy, P: FieldParameters> EdDecompressCols<V, P> {
         builder.receive_syscall(
             self.shard,
             self.clk,
+            self.nonce,
             AB::F::from_canonical_u32(SyscallCode::ED_DECOMPRESS.syscall_id()),
             self.ptr,
             self.sign,
@@ -254,14 +356,72 @@ impl<V: Copy, P: FieldParameters> EdDecompressCols<V, P> {
     }
 }
 
-#[derive(Default)]
+/// Ties a base-field limb width to the syscall that emits it and the events
+/// `ExecutionRecord::precompile_events` stores under that syscall, the same way `MemCopyWidth`
+/// ties a copy width to its own syscall. A second Edwards curve (e.g. Ed448) would get its own
+/// `SyscallCode` and implement this for its base field's limb width -- see the note on
+/// `EdDecompressChip` below: that part isn't done yet, only this plumbing is.
+pub trait EdDecompressWidth: LimbWidth {
+    const SYSCALL_CODE: SyscallCode;
+
+    fn wrap_event(event: EdDecompressEvent<Self>) -> PrecompileEvent;
+
+    fn decompress_events(record: &ExecutionRecord) -> Vec<&EdDecompressEvent<Self>>;
+}
+
+impl EdDecompressWidth for DEFAULT_NUM_LIMBS_T {
+    const SYSCALL_CODE: SyscallCode = SyscallCode::ED_DECOMPRESS;
+
+    fn wrap_event(event: EdDecompressEvent<Self>) -> PrecompileEvent {
+        PrecompileEvent::EdDecompress(event)
+    }
+
+    fn decompress_events(record: &ExecutionRecord) -> Vec<&EdDecompressEvent<Self>> {
+        record
+            .get_precompile_events(Self::SYSCALL_CODE)
+            .iter()
+            .map(|event| match event {
+                PrecompileEvent::EdDecompress(event) => event,
+                _ => unreachable!("ED_DECOMPRESS syscall code maps only to EdDecompress events"),
+            })
+            .collect()
+    }
+}
+
+/// Recovers a curve25519-dalek-style compressed edwards point's `x` coordinate (as little-endian
+/// bytes, unpadded) from its 32-byte `y` encoding and sign bit. This is ed25519's own wire format
+/// and math; a curve whose encoding doesn't fit `CompressedEdwardsY` (e.g. Ed448) supplies its own
+/// function of this shape to [`EdDecompressChip::with_curve_ops`] instead of this one.
+fn ed25519_decompress_x_bytes(y_bytes: &[u8], sign: bool) -> Vec<u8> {
+    let mut compressed_edwards_y: [u8; 32] = y_bytes.try_into().unwrap();
+    compressed_edwards_y[31] &= 0b0111_1111;
+    compressed_edwards_y[31] |= (sign as u8) << 7;
+    decompress(&CompressedEdwardsY(compressed_edwards_y))
+        .x
+        .to_bytes_le()
+}
+
+/// Not yet wired up to a second curve: `decompress_x_bytes`/`sqrt` let `execute`/`generate_trace`
+/// dispatch through curve-specific function pointers instead of importing ed25519's directly, but
+/// no Ed448 (or other) `EdwardsParameters` impl, base-field `FieldParameters` impl, sqrt routine,
+/// or `SyscallCode` variant exists anywhere in this tree to plug in here -- this struct is
+/// generic-over-curve plumbing only, not Ed448 support itself. Adding real Ed448 support needs all
+/// four of those pieces built first; this backlog item should stay open until they are.
 pub struct EdDecompressChip<E> {
+    decompress_x_bytes: fn(&[u8], bool) -> Vec<u8>,
+    sqrt: fn(&BigUint) -> BigUint,
     _phantom: PhantomData<E>,
 }
 
-// TODO(FG): This function is already generic in NB_LIMBS, but the ed_decompress_events record is not
-impl<F: FieldParameters<NB_LIMBS = DEFAULT_NUM_LIMBS_T>, E: EdwardsParameters<BaseField = F>>
-    Syscall for EdDecompressChip<E>
+impl<E: EdwardsParameters> Default for EdDecompressChip<E> {
+    fn default() -> Self {
+        Self::with_curve_ops(ed25519_decompress_x_bytes, ed25519_sqrt)
+    }
+}
+
+impl<F: FieldParameters, E: EdwardsParameters<BaseField = F>> Syscall for EdDecompressChip<E>
+where
+    BaseLimbWidth<E>: EdDecompressWidth,
 {
     fn execute(&self, rt: &mut SyscallContext<'_>, arg1: u32, sign: u32) -> Option<u32> {
         let start_clk = rt.clk;
@@ -270,10 +430,11 @@ impl<F: FieldParameters<NB_LIMBS = DEFAULT_NUM_LIMBS_T>, E: EdwardsParameters<Ba
         assert!(sign <= 1, "Sign bit must be 0 or 1.");
         let sign = sign as u8;
 
-        let (y_memory_records_vec, y_vec) = rt.mr_slice(
-            slice_ptr + (BYTES_COMPRESSED_CURVEPOINT::<BaseLimbWidth<E>>::USIZE as u32),
-            WORDS_FIELD_ELEMENT::<BaseLimbWidth<E>>::USIZE,
-        );
+        let y_ptr = slice_ptr + (BYTES_COMPRESSED_CURVEPOINT::<BaseLimbWidth<E>>::USIZE as u32);
+        let (y_memory_records_vec, y_vec) =
+            rt.mr_slice(y_ptr, WORDS_FIELD_ELEMENT::<BaseLimbWidth<E>>::USIZE);
+        rt.record_mut()
+            .update_local_memory_access_reads(y_ptr, &y_memory_records_vec);
         let y_memory_records: Array<MemoryReadRecord, WORDS_FIELD_ELEMENT<BaseLimbWidth<E>>> =
             (&y_memory_records_vec[..]).try_into().unwrap();
 
@@ -281,19 +442,10 @@ impl<F: FieldParameters<NB_LIMBS = DEFAULT_NUM_LIMBS_T>, E: EdwardsParameters<Ba
 
         let y_bytes = words_to_bytes_le::<BYTES_COMPRESSED_CURVEPOINT<BaseLimbWidth<E>>>(&y_vec);
 
-        // Copy bytes into another array so we can modify the last byte and make CompressedEdwardsY,
-        // which we'll use to compute the expected X.
-        // Re-insert sign bit into last bit of Y for CompressedEdwardsY format
-        let mut compressed_edwards_y = y_bytes;
-        compressed_edwards_y[compressed_edwards_y.len() - 1] &= 0b0111_1111;
-        compressed_edwards_y[compressed_edwards_y.len() - 1] |= sign << 7;
-
         // Compute actual decompressed X
-        let compressed_y = CompressedEdwardsY(compressed_edwards_y);
-        let decompressed = decompress(&compressed_y);
+        let mut decompressed_x_bytes = (self.decompress_x_bytes)(&y_bytes, sign_bool);
 
         let bytes_field_elt = BYTES_FIELD_ELEMENT::<BaseLimbWidth<E>>::USIZE;
-        let mut decompressed_x_bytes = decompressed.x.to_bytes_le();
         decompressed_x_bytes.resize(bytes_field_elt, 0u8);
 
         let decompressed_x_words =
@@ -301,22 +453,27 @@ impl<F: FieldParameters<NB_LIMBS = DEFAULT_NUM_LIMBS_T>, E: EdwardsParameters<Ba
 
         // Write decompressed X into slice
         let x_memory_records_vec = rt.mw_slice(slice_ptr, &decompressed_x_words);
+        rt.record_mut()
+            .update_local_memory_access_writes(slice_ptr, &x_memory_records_vec);
         let x_memory_records: Array<MemoryWriteRecord, WORDS_FIELD_ELEMENT<BaseLimbWidth<E>>> =
             (&x_memory_records_vec[..]).try_into().unwrap();
 
         let shard = rt.current_shard();
-        rt.record_mut()
-            .ed_decompress_events
-            .push(EdDecompressEvent {
-                shard,
-                clk: start_clk,
-                ptr: slice_ptr,
-                sign: sign_bool,
-                y_bytes: y_bytes.into(),
-                decompressed_x_bytes: (&decompressed_x_bytes[..]).try_into().unwrap(),
-                x_memory_records,
-                y_memory_records,
-            });
+        let event = EdDecompressEvent {
+            shard,
+            clk: start_clk,
+            nonce: 0,
+            ptr: slice_ptr,
+            sign: sign_bool,
+            y_bytes: y_bytes.into(),
+            decompressed_x_bytes: (&decompressed_x_bytes[..]).try_into().unwrap(),
+            x_memory_records,
+            y_memory_records,
+        };
+        rt.record_mut().add_precompile_event(
+            BaseLimbWidth::<E>::SYSCALL_CODE,
+            BaseLimbWidth::<E>::wrap_event(event),
+        );
         None
     }
 
@@ -327,13 +484,28 @@ impl<F: FieldParameters<NB_LIMBS = DEFAULT_NUM_LIMBS_T>, E: EdwardsParameters<Ba
 
 impl<E: EdwardsParameters> EdDecompressChip<E> {
     pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds a chip around `decompress_x_bytes`/`sqrt`, the curve-specific math `execute`/
+    /// `generate_trace` dispatch through instead of importing ed25519's directly -- a second curve
+    /// (e.g. Ed448) plugs in its own pair here rather than forking this chip.
+    pub fn with_curve_ops(
+        decompress_x_bytes: fn(&[u8], bool) -> Vec<u8>,
+        sqrt: fn(&BigUint) -> BigUint,
+    ) -> Self {
         Self {
+            decompress_x_bytes,
+            sqrt,
             _phantom: PhantomData,
         }
     }
 }
 
-impl<F: PrimeField32, E: EdwardsParameters> MachineAir<F> for EdDecompressChip<E> {
+impl<F: PrimeField32, E: EdwardsParameters> MachineAir<F> for EdDecompressChip<E>
+where
+    BaseLimbWidth<E>: EdDecompressWidth,
+{
     type Record = ExecutionRecord;
 
     type Program = Program;
@@ -347,24 +519,36 @@ impl<F: PrimeField32, E: EdwardsParameters> MachineAir<F> for EdDecompressChip<E
         input: &ExecutionRecord,
         output: &mut ExecutionRecord,
     ) -> RowMajorMatrix<F> {
+        let events = BaseLimbWidth::<E>::decompress_events(input);
+
+        let modulus = E::BaseField::modulus();
+        let ys: Vec<BigUint> = events
+            .iter()
+            .map(|event| BigUint::from_bytes_le(&event.y_bytes))
+            .collect();
+        let v_invs = batch_invert_denominators::<E>(&ys, &modulus);
+
         let mut rows = Vec::new();
 
-        for i in 0..input.ed_decompress_events.len() {
-            let event = &input.ed_decompress_events[i];
+        for (event, v_inv) in events.iter().copied().zip(v_invs.iter()) {
             let mut row = vec![F::zero(); size_of::<EdDecompressCols<u8, E::BaseField>>()];
             let cols: &mut EdDecompressCols<F, E::BaseField> = row.as_mut_slice().borrow_mut();
-            cols.populate::<E>(event, output);
+            cols.populate::<E>(event.nonce as usize, event, output, self.sqrt, v_inv);
 
             rows.push(row);
         }
 
-        pad_vec_rows(&mut rows, || {
+        // `y = 0` produces the same padding row every time, so compute it once and clone it
+        // rather than re-running `populate_field_ops` per pad row.
+        let padding_row = {
             let mut row = vec![F::zero(); size_of::<EdDecompressCols<u8, E::BaseField>>()];
             let cols: &mut EdDecompressCols<F, E::BaseField> = row.as_mut_slice().borrow_mut();
             let zero = BigUint::zero();
-            cols.populate_field_ops::<E>(&mut vec![], 0, &zero);
+            let zero_v_inv = mod_inverse::<E>(&zero, &modulus);
+            cols.populate_field_ops::<E>(&mut vec![], 0, &zero, self.sqrt, &zero_v_inv);
             row
-        });
+        };
+        pad_vec_rows(&mut rows, || padding_row.clone());
 
         RowMajorMatrix::new(
             rows.into_iter().flatten().collect::<Vec<_>>(),
@@ -373,7 +557,7 @@ impl<F: PrimeField32, E: EdwardsParameters> MachineAir<F> for EdDecompressChip<E
     }
 
     fn included(&self, shard: &Self::Record) -> bool {
-        !shard.ed_decompress_events.is_empty()
+        !BaseLimbWidth::<E>::decompress_events(shard).is_empty()
     }
 }
 
@@ -391,6 +575,16 @@ where
         let main = builder.main();
         let row = main.row_slice(0);
         let row: &EdDecompressCols<AB::Var, E::BaseField> = (*row).borrow();
+        let next = main.row_slice(1);
+        let next: &EdDecompressCols<AB::Var, E::BaseField> = (*next).borrow();
+
+        // The nonce of the first row is 0, and nonces increment by 1 for each subsequent real row.
+        builder.when_first_row().assert_zero(row.nonce);
+        builder
+            .when_transition()
+            .when(next.is_real)
+            .assert_eq(row.nonce + AB::F::one(), next.nonce);
+
         row.eval::<AB, E>(builder);
     }
 }
@@ -0,0 +1,352 @@
+use crate::air::MachineAir;
+use crate::air::SP1AirBuilder;
+use crate::bytes::ByteLookupEvent;
+use crate::memory::MemoryCols;
+use crate::memory::MemoryReadCols;
+use crate::memory::MemoryWriteCols;
+use crate::operations::field::field_op::FieldOpCols;
+use crate::operations::field::field_op::FieldOperation;
+use crate::operations::field::params::Limbs;
+use crate::operations::field::params::WORDS_FIELD_ELEMENT;
+use crate::runtime::ExecutionRecord;
+use crate::runtime::MemoryReadRecord;
+use crate::runtime::MemoryWriteRecord;
+use crate::runtime::Program;
+use crate::syscall::precompiles::SyscallContext;
+use crate::utils::bytes_to_words_le;
+use crate::utils::ec::field::FieldParameters;
+use crate::utils::ec::field::WithFieldArithmetic;
+use crate::utils::limbs_from_prev_access;
+use crate::utils::pad_vec_rows;
+use core::borrow::{Borrow, BorrowMut};
+use core::mem::size_of;
+use hybrid_array::typenum::Unsigned;
+use hybrid_array::Array;
+use num::BigUint;
+use num::Zero;
+use p3_air::AirBuilder;
+use p3_air::{Air, BaseAir};
+use p3_field::AbstractField;
+use p3_field::PrimeField32;
+use p3_matrix::dense::RowMajorMatrix;
+use p3_matrix::MatrixRowSlices;
+use p3_maybe_rayon::prelude::IntoParallelRefIterator;
+use p3_maybe_rayon::prelude::ParallelIterator;
+use serde::Deserialize;
+use serde::Serialize;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use tracing::instrument;
+use wp1_derive::AlignedBorrow;
+
+/// A set of columns to compute `acc = acc + a * b mod p` where `acc`, `a`, `b` are elements of
+/// some prime field `Fp`.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct FieldMacCols<T, FP: FieldParameters> {
+    pub is_real: T,
+    /// This row's index within the shard's trace for this chip, constrained to match the
+    /// `nonce` sent to `receive_syscall` so no two rows can collide in the cross-table argument.
+    pub nonce: T,
+    pub shard: T,
+    pub clk: T,
+    pub arg1_ptr: T,
+    pub arg2_ptr: T,
+    pub arg3_ptr: T,
+    pub arg1_access: Array<MemoryWriteCols<T>, WORDS_FIELD_ELEMENT<FP::NB_LIMBS>>,
+    pub arg2_access: Array<MemoryReadCols<T>, WORDS_FIELD_ELEMENT<FP::NB_LIMBS>>,
+    pub arg3_access: Array<MemoryReadCols<T>, WORDS_FIELD_ELEMENT<FP::NB_LIMBS>>,
+    pub(crate) a_mul_b: FieldOpCols<T, FP::NB_LIMBS>,
+    pub(crate) acc_plus_ab: FieldOpCols<T, FP::NB_LIMBS>,
+}
+
+#[derive(Default)]
+pub struct FieldMacChip<FP: FieldParameters> {
+    _marker: PhantomData<FP>,
+}
+
+impl<FP: FieldParameters> FieldMacChip<FP> {
+    pub fn new() -> Self {
+        Self {
+            _marker: Default::default(),
+        }
+    }
+}
+
+/// Fp multiply-accumulate event: `acc = acc + a * b mod p`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldMacEvent<FP: FieldParameters> {
+    pub shard: u32,
+    pub clk: u32,
+    /// This event's index within its shard's per-syscall vector, stamped by
+    /// [`ExecutionRecord::assign_nonces`](crate::runtime::ExecutionRecord::assign_nonces) once
+    /// the shard is finalized. Defaults to `0` so older serialized records still deserialize.
+    #[serde(default)]
+    pub nonce: u32,
+    pub arg1_ptr: u32,
+    #[serde(with = "crate::utils::array_serde::ArraySerde")]
+    pub acc: Array<u32, WORDS_FIELD_ELEMENT<FP::NB_LIMBS>>,
+    pub arg2_ptr: u32,
+    #[serde(with = "crate::utils::array_serde::ArraySerde")]
+    pub a: Array<u32, WORDS_FIELD_ELEMENT<FP::NB_LIMBS>>,
+    pub arg3_ptr: u32,
+    #[serde(with = "crate::utils::array_serde::ArraySerde")]
+    pub b: Array<u32, WORDS_FIELD_ELEMENT<FP::NB_LIMBS>>,
+    #[serde(with = "crate::utils::array_serde::ArraySerde")]
+    pub arg1_memory_records: Array<MemoryWriteRecord, WORDS_FIELD_ELEMENT<FP::NB_LIMBS>>,
+    #[serde(with = "crate::utils::array_serde::ArraySerde")]
+    pub arg2_memory_records: Array<MemoryReadRecord, WORDS_FIELD_ELEMENT<FP::NB_LIMBS>>,
+    #[serde(with = "crate::utils::array_serde::ArraySerde")]
+    pub arg3_memory_records: Array<MemoryReadRecord, WORDS_FIELD_ELEMENT<FP::NB_LIMBS>>,
+}
+
+pub fn create_fp_mac_event<FP: FieldParameters>(
+    rt: &mut SyscallContext<'_>,
+    arg1: u32,
+    arg2: u32,
+    arg3: u32,
+) -> FieldMacEvent<FP> {
+    let start_clk = rt.clk;
+    let arg1_ptr = arg1;
+    let arg2_ptr = arg2;
+    let arg3_ptr = arg3;
+    assert!(arg1_ptr % 4 == 0);
+    assert!(arg2_ptr % 4 == 0);
+    assert!(arg3_ptr % 4 == 0);
+
+    let words_len = WORDS_FIELD_ELEMENT::<FP::NB_LIMBS>::USIZE;
+
+    let (arg2_memory_records_vec, a_vec) = rt.mr_slice(arg2_ptr, words_len);
+    rt.record_mut()
+        .update_local_memory_access_reads(arg2_ptr, &arg2_memory_records_vec);
+    let arg2_memory_records = (&arg2_memory_records_vec[..]).try_into().unwrap();
+    let a: Array<u32, _> = (&a_vec[..]).try_into().unwrap();
+    let a_int = BigUint::from_slice(&a);
+
+    let (arg3_memory_records_vec, b_vec) = rt.mr_slice(arg3_ptr, words_len);
+    rt.record_mut()
+        .update_local_memory_access_reads(arg3_ptr, &arg3_memory_records_vec);
+    let arg3_memory_records = (&arg3_memory_records_vec[..]).try_into().unwrap();
+    let b: Array<u32, _> = (&b_vec[..]).try_into().unwrap();
+    let b_int = BigUint::from_slice(&b);
+
+    let acc: Array<u32, _> = (&rt.slice_unsafe(arg1_ptr, words_len)[..])
+        .try_into()
+        .unwrap();
+    let acc_int = BigUint::from_slice(&acc);
+
+    let result_int = (acc_int + (&a_int * &b_int)) % FP::modulus();
+    let result_bytes = FP::to_limbs(&result_int);
+    let result_words = bytes_to_words_le::<WORDS_FIELD_ELEMENT<FP::NB_LIMBS>>(&result_bytes);
+
+    // When we write to arg1, we want the clk to be incremented because arg1 could alias arg2/arg3.
+    rt.clk += 1;
+    let arg1_memory_records_vec = rt.mw_slice(arg1_ptr, &result_words.into());
+    rt.record_mut()
+        .update_local_memory_access_writes(arg1_ptr, &arg1_memory_records_vec);
+    let arg1_memory_records = (&arg1_memory_records_vec[..]).try_into().unwrap();
+
+    FieldMacEvent {
+        shard: rt.current_shard(),
+        clk: start_clk,
+        nonce: 0,
+        arg1_ptr,
+        acc,
+        arg2_ptr,
+        a,
+        arg3_ptr,
+        b,
+        arg1_memory_records,
+        arg2_memory_records,
+        arg3_memory_records,
+    }
+}
+
+impl<F: PrimeField32, FP: FieldParameters + WithFieldArithmetic> MachineAir<F>
+    for FieldMacChip<FP>
+{
+    type Record = ExecutionRecord;
+    type Program = Program;
+
+    fn name(&self) -> String {
+        format!("{:?}FieldMac", FP::FIELD_TYPE)
+    }
+
+    #[instrument(name = "generate field mac trace", level = "debug", skip_all)]
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        output: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        // collects the events based on the field type.
+        let events = FP::mac_events(input);
+
+        let (mut rows, new_byte_lookup_events): (Vec<_>, Vec<Vec<ByteLookupEvent>>) = events
+            .par_iter()
+            .map(|event| {
+                let words_len = WORDS_FIELD_ELEMENT::<FP::NB_LIMBS>::USIZE;
+                let mut row = vec![F::zero(); size_of::<FieldMacCols<u8, FP>>()];
+                let cols: &mut FieldMacCols<F, FP> = row.as_mut_slice().borrow_mut();
+
+                // Populate basic columns.
+                cols.is_real = F::one();
+                cols.nonce = F::from_canonical_u32(event.nonce);
+                cols.shard = F::from_canonical_u32(event.shard);
+                cols.clk = F::from_canonical_u32(event.clk);
+                cols.arg1_ptr = F::from_canonical_u32(event.arg1_ptr);
+                cols.arg2_ptr = F::from_canonical_u32(event.arg2_ptr);
+                cols.arg3_ptr = F::from_canonical_u32(event.arg3_ptr);
+
+                // Decode field elements.
+                let acc_int = BigUint::from_slice(&event.acc);
+                let a_int = BigUint::from_slice(&event.a);
+                let b_int = BigUint::from_slice(&event.b);
+
+                let ab = cols
+                    .a_mul_b
+                    .populate::<FP>(&a_int, &b_int, FieldOperation::Mul);
+                cols.acc_plus_ab
+                    .populate::<FP>(&acc_int, &ab, FieldOperation::Add);
+
+                // Populate the memory access columns.
+                let mut new_byte_lookup_events = Vec::new();
+                for i in 0..words_len {
+                    cols.arg2_access[i]
+                        .populate(event.arg2_memory_records[i], &mut new_byte_lookup_events);
+                }
+                for i in 0..words_len {
+                    cols.arg3_access[i]
+                        .populate(event.arg3_memory_records[i], &mut new_byte_lookup_events);
+                }
+                for i in 0..words_len {
+                    cols.arg1_access[i]
+                        .populate(event.arg1_memory_records[i], &mut new_byte_lookup_events);
+                }
+
+                (row, new_byte_lookup_events)
+            })
+            .unzip();
+
+        for byte_lookup_events in new_byte_lookup_events {
+            output.add_byte_lookup_events(byte_lookup_events);
+        }
+
+        pad_vec_rows(&mut rows, || {
+            let mut row = vec![F::zero(); size_of::<FieldMacCols<u8, FP>>()];
+            let cols: &mut FieldMacCols<F, FP> = row.as_mut_slice().borrow_mut();
+            let zero = BigUint::zero();
+            let ab = cols.a_mul_b.populate::<FP>(&zero, &zero, FieldOperation::Mul);
+            cols.acc_plus_ab
+                .populate::<FP>(&zero, &ab, FieldOperation::Add);
+            row
+        });
+
+        // Convert the trace to a row major matrix.
+        RowMajorMatrix::new(
+            rows.into_iter().flatten().collect::<Vec<_>>(),
+            size_of::<FieldMacCols<u8, FP>>(),
+        )
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        !shard.get_precompile_events(FP::mac_syscall_code()).is_empty()
+    }
+}
+
+impl<F, FP: FieldParameters> BaseAir<F> for FieldMacChip<FP> {
+    fn width(&self) -> usize {
+        size_of::<FieldMacCols<u8, FP>>()
+    }
+}
+
+impl<AB, FP: FieldParameters> Air<AB> for FieldMacChip<FP>
+where
+    AB: SP1AirBuilder,
+{
+    fn eval(&self, builder: &mut AB) {
+        let words_len = WORDS_FIELD_ELEMENT::<FP::NB_LIMBS>::USIZE;
+        let main = builder.main();
+        let row: &FieldMacCols<AB::Var, FP> = main.row_slice(0).borrow();
+        let next: &FieldMacCols<AB::Var, FP> = main.row_slice(1).borrow();
+
+        // The nonce of the first row is 0, and nonces increment by 1 for each subsequent real row.
+        builder.when_first_row().assert_zero(row.nonce);
+        builder
+            .when_transition()
+            .when(next.is_real)
+            .assert_eq(row.nonce + AB::F::one(), next.nonce);
+
+        let acc: Limbs<_, FP::NB_LIMBS> = limbs_from_prev_access(&row.arg1_access[0..words_len]);
+        let a: Limbs<_, FP::NB_LIMBS> = limbs_from_prev_access(&row.arg2_access[0..words_len]);
+        let b: Limbs<_, FP::NB_LIMBS> = limbs_from_prev_access(&row.arg3_access[0..words_len]);
+
+        row.a_mul_b
+            .eval::<AB, FP, _, _>(builder, &a, &b, FieldOperation::Mul);
+        row.acc_plus_ab
+            .eval::<AB, FP, _, _>(builder, &acc, &row.a_mul_b.result, FieldOperation::Add);
+
+        // Constraint self.arg1_access.value = [self.acc_plus_ab.result]
+        // This is to ensure that arg1_access is updated with the new value.
+        for i in 0..FP::NB_LIMBS::USIZE {
+            builder.when(row.is_real).assert_eq(
+                row.acc_plus_ab.result[i],
+                row.arg1_access[i / 4].value()[i % 4],
+            );
+        }
+
+        for i in 0..words_len {
+            builder.constraint_memory_access(
+                row.shard,
+                row.clk, // clk + 0 -> Memory
+                row.arg2_ptr + AB::F::from_canonical_u32(i as u32 * 4),
+                &row.arg2_access[i],
+                row.is_real,
+            );
+        }
+        for i in 0..words_len {
+            builder.constraint_memory_access(
+                row.shard,
+                row.clk, // clk + 0 -> Memory
+                row.arg3_ptr + AB::F::from_canonical_u32(i as u32 * 4),
+                &row.arg3_access[i],
+                row.is_real,
+            );
+        }
+        for i in 0..words_len {
+            builder.constraint_memory_access(
+                row.shard,
+                row.clk + AB::F::from_canonical_u32(1), // The clk for arg1 is moved by 1.
+                row.arg1_ptr + AB::F::from_canonical_u32(i as u32 * 4),
+                &row.arg1_access[i],
+                row.is_real,
+            );
+        }
+
+        // Fetch the syscall id for the field type.
+        let syscall_id_fe = AB::F::from_canonical_u32(FP::mac_syscall_code().syscall_id());
+
+        builder.receive_syscall(
+            row.shard,
+            row.clk,
+            row.nonce,
+            syscall_id_fe,
+            row.arg1_ptr,
+            row.arg2_ptr,
+            row.is_real,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils;
+    use crate::utils::tests::BLS12381_FP_MAC_ELF;
+    use crate::Program;
+
+    #[test]
+    fn test_bls12381_fp_mac_simple() {
+        utils::setup_logger();
+        let program = Program::from(BLS12381_FP_MAC_ELF);
+        utils::run_test(program).unwrap();
+    }
+}
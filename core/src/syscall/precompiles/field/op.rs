@@ -0,0 +1,381 @@
+use crate::air::MachineAir;
+use crate::air::SP1AirBuilder;
+use crate::bytes::ByteLookupEvent;
+use crate::memory::MemoryCols;
+use crate::memory::MemoryReadCols;
+use crate::memory::MemoryWriteCols;
+use crate::operations::field::field_op::FieldOpCols;
+use crate::operations::field::field_op::FieldOperation;
+use crate::operations::field::params::Limbs;
+use crate::operations::field::params::WORDS_FIELD_ELEMENT;
+use crate::runtime::ExecutionRecord;
+use crate::runtime::MemoryReadRecord;
+use crate::runtime::MemoryWriteRecord;
+use crate::runtime::Program;
+use crate::runtime::SyscallCode;
+use crate::syscall::precompiles::SyscallContext;
+use crate::utils::bytes_to_words_le;
+use crate::utils::ec::field::FieldParameters;
+use crate::utils::ec::field::WithFieldArithmetic;
+use crate::utils::limbs_from_prev_access;
+use crate::utils::pad_vec_rows;
+use core::borrow::{Borrow, BorrowMut};
+use core::mem::size_of;
+use hybrid_array::typenum::Unsigned;
+use hybrid_array::Array;
+use num::BigUint;
+use num::Zero;
+use p3_air::AirBuilder;
+use p3_air::{Air, BaseAir};
+use p3_field::AbstractField;
+use p3_field::PrimeField32;
+use p3_matrix::dense::RowMajorMatrix;
+use p3_matrix::MatrixRowSlices;
+use p3_maybe_rayon::prelude::IntoParallelRefIterator;
+use p3_maybe_rayon::prelude::ParallelIterator;
+use serde::Deserialize;
+use serde::Serialize;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use tracing::instrument;
+use wp1_derive::AlignedBorrow;
+
+/// A set of columns to compute a binary field operation `p OP q` where `p`, `q` are elements of
+/// some prime field `Fp` and `OP` is one of `Add`/`Sub`/`Mul`.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct FieldArithCols<T, FP: FieldParameters> {
+    pub is_real: T,
+    /// This row's index within the shard's trace for this chip, constrained to match the
+    /// `nonce` sent to `receive_syscall` so no two rows can collide in the cross-table argument.
+    pub nonce: T,
+    pub shard: T,
+    pub clk: T,
+    pub p_ptr: T,
+    pub q_ptr: T,
+    pub p_access: Array<MemoryWriteCols<T>, WORDS_FIELD_ELEMENT<FP::NB_LIMBS>>,
+    pub q_access: Array<MemoryReadCols<T>, WORDS_FIELD_ELEMENT<FP::NB_LIMBS>>,
+    pub(crate) p_op_q: FieldOpCols<T, FP::NB_LIMBS>,
+}
+
+/// A binary field arithmetic event: `result = p OP q mod p_modulus`, for whichever `OP` the
+/// owning [`FieldOpChip`] is configured with. Shared across Add/Sub/Mul so that adding a new
+/// field operation does not require a new event type.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldArithEvent<FP: FieldParameters> {
+    pub shard: u32,
+    pub clk: u32,
+    /// This event's index within its shard's per-syscall vector, stamped by
+    /// [`ExecutionRecord::assign_nonces`](crate::runtime::ExecutionRecord::assign_nonces) once
+    /// the shard is finalized. Defaults to `0` so older serialized records still deserialize.
+    #[serde(default)]
+    pub nonce: u32,
+    pub p_ptr: u32,
+    #[serde(with = "crate::utils::array_serde::ArraySerde")]
+    pub p: Array<u32, WORDS_FIELD_ELEMENT<FP::NB_LIMBS>>,
+    pub q_ptr: u32,
+    #[serde(with = "crate::utils::array_serde::ArraySerde")]
+    pub q: Array<u32, WORDS_FIELD_ELEMENT<FP::NB_LIMBS>>,
+    #[serde(with = "crate::utils::array_serde::ArraySerde")]
+    pub p_memory_records: Array<MemoryWriteRecord, WORDS_FIELD_ELEMENT<FP::NB_LIMBS>>,
+    #[serde(with = "crate::utils::array_serde::ArraySerde")]
+    pub q_memory_records: Array<MemoryReadRecord, WORDS_FIELD_ELEMENT<FP::NB_LIMBS>>,
+}
+
+/// Performs `result = p OP q mod FP::modulus()`, aliasing-safe for `p == q`, exactly like
+/// `create_fp_sub_event` used to.
+pub fn create_fp_arith_event<FP: FieldParameters>(
+    rt: &mut SyscallContext<'_>,
+    arg1: u32,
+    arg2: u32,
+    op: FieldOperation,
+) -> FieldArithEvent<FP> {
+    let start_clk = rt.clk;
+    let p_ptr = arg1;
+    let q_ptr = arg2;
+    assert!(p_ptr % 4 == 0);
+    assert!(q_ptr % 4 == 0);
+
+    let words_len = WORDS_FIELD_ELEMENT::<FP::NB_LIMBS>::USIZE;
+
+    let (q_memory_records_vec, q_vec) = rt.mr_slice(q_ptr, words_len);
+    rt.record_mut()
+        .update_local_memory_access_reads(q_ptr, &q_memory_records_vec);
+    let q_memory_records = (&q_memory_records_vec[..]).try_into().unwrap();
+    let q: Array<u32, _> = (&q_vec[..]).try_into().unwrap();
+    let q_int = BigUint::from_slice(&q);
+
+    let p: Array<u32, _> = (&rt.slice_unsafe(p_ptr, words_len)[..]).try_into().unwrap();
+    let p_int = BigUint::from_slice(&p);
+    let result_int = match op {
+        FieldOperation::Add => (&p_int + &q_int) % FP::modulus(),
+        FieldOperation::Sub => (FP::modulus() + &p_int - &q_int) % FP::modulus(),
+        FieldOperation::Mul => (&p_int * &q_int) % FP::modulus(),
+        _ => panic!("FieldOpChip only supports Add/Sub/Mul"),
+    };
+    let result_bytes = FP::to_limbs(&result_int);
+    let result_words = bytes_to_words_le::<WORDS_FIELD_ELEMENT<FP::NB_LIMBS>>(&result_bytes);
+
+    // When we write to p, we want the clk to be incremented because p and q could be the same.
+    rt.clk += 1;
+    let p_memory_records_vec = rt.mw_slice(p_ptr, &result_words.into());
+    rt.record_mut()
+        .update_local_memory_access_writes(p_ptr, &p_memory_records_vec);
+    let p_memory_records = (&p_memory_records_vec[..]).try_into().unwrap();
+
+    FieldArithEvent {
+        shard: rt.current_shard(),
+        clk: start_clk,
+        nonce: 0,
+        p_ptr,
+        p,
+        q_ptr,
+        q,
+        p_memory_records,
+        q_memory_records,
+    }
+}
+
+/// A configurable field-arithmetic precompile that proves `p = p OP q mod FP::modulus()` for
+/// whichever prime field `FP` and binary operation `op` it is instantiated with. Replaces the
+/// one-chip-per-operation-per-field copy-paste that used to live in `sub.rs`/`add.rs`/`mul.rs`.
+pub struct FieldOpChip<FP: FieldParameters> {
+    op: FieldOperation,
+    _marker: PhantomData<FP>,
+}
+
+impl<FP: FieldParameters> FieldOpChip<FP> {
+    pub fn new(op: FieldOperation) -> Self {
+        assert!(matches!(
+            op,
+            FieldOperation::Add | FieldOperation::Sub | FieldOperation::Mul
+        ));
+        Self {
+            op,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<F: PrimeField32, FP: FieldParameters + WithFieldArithmetic> MachineAir<F> for FieldOpChip<FP> {
+    type Record = ExecutionRecord;
+    type Program = Program;
+
+    fn name(&self) -> String {
+        format!("{:?}Field{:?}", FP::FIELD_TYPE, self.op)
+    }
+
+    #[instrument(name = "generate field op trace", level = "debug", skip_all)]
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        output: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        // Collects the events based on the field type and configured operation.
+        let events = match self.op {
+            FieldOperation::Add => FP::add_events(input),
+            FieldOperation::Sub => FP::sub_events(input),
+            FieldOperation::Mul => FP::mul_events(input),
+            _ => unreachable!(),
+        };
+
+        let (mut rows, new_byte_lookup_events): (Vec<_>, Vec<Vec<ByteLookupEvent>>) = events
+            .par_iter()
+            .map(|event| {
+                let words_len = WORDS_FIELD_ELEMENT::<FP::NB_LIMBS>::USIZE;
+                let mut row = vec![F::zero(); size_of::<FieldArithCols<u8, FP>>()];
+                let cols: &mut FieldArithCols<F, FP> = row.as_mut_slice().borrow_mut();
+
+                // Populate basic columns.
+                cols.is_real = F::one();
+                cols.nonce = F::from_canonical_u32(event.nonce);
+                cols.shard = F::from_canonical_u32(event.shard);
+                cols.clk = F::from_canonical_u32(event.clk);
+                cols.p_ptr = F::from_canonical_u32(event.p_ptr);
+                cols.q_ptr = F::from_canonical_u32(event.q_ptr);
+
+                // Decode field elements.
+                let p_int = BigUint::from_slice(&event.p);
+                let q_int = BigUint::from_slice(&event.q);
+                cols.p_op_q.populate::<FP>(&p_int, &q_int, self.op);
+
+                // Populate the memory access columns.
+                let mut new_byte_lookup_events = Vec::new();
+                for i in 0..words_len {
+                    cols.q_access[i]
+                        .populate(event.q_memory_records[i], &mut new_byte_lookup_events);
+                }
+                for i in 0..words_len {
+                    cols.p_access[i]
+                        .populate(event.p_memory_records[i], &mut new_byte_lookup_events);
+                }
+
+                (row, new_byte_lookup_events)
+            })
+            .unzip();
+
+        for byte_lookup_events in new_byte_lookup_events {
+            output.add_byte_lookup_events(byte_lookup_events);
+        }
+
+        pad_vec_rows(&mut rows, || {
+            let mut row = vec![F::zero(); size_of::<FieldArithCols<u8, FP>>()];
+            let cols: &mut FieldArithCols<F, FP> = row.as_mut_slice().borrow_mut();
+            let zero = BigUint::zero();
+            cols.p_op_q.populate::<FP>(&zero, &zero, self.op);
+            row
+        });
+
+        // Convert the trace to a row major matrix.
+        RowMajorMatrix::new(
+            rows.into_iter().flatten().collect::<Vec<_>>(),
+            size_of::<FieldArithCols<u8, FP>>(),
+        )
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        !shard
+            .get_precompile_events(FP::syscall_code(self.op))
+            .is_empty()
+    }
+
+    /// Replays the same `par_iter` as `generate_trace` but only populates enough of the columns
+    /// to emit this shard's `ByteLookupEvent`s into `output`, without allocating or returning the
+    /// row matrix. This lets the prover run a cheap dependency pass first and defer the heavier
+    /// `generate_trace` matrix build (dropped immediately after commitment) to the proving stage.
+    fn generate_dependencies(&self, input: &ExecutionRecord, output: &mut ExecutionRecord) {
+        let events = match self.op {
+            FieldOperation::Add => FP::add_events(input),
+            FieldOperation::Sub => FP::sub_events(input),
+            FieldOperation::Mul => FP::mul_events(input),
+            _ => unreachable!(),
+        };
+
+        let new_byte_lookup_events: Vec<Vec<ByteLookupEvent>> = events
+            .par_iter()
+            .map(|event| {
+                let words_len = WORDS_FIELD_ELEMENT::<FP::NB_LIMBS>::USIZE;
+                let mut row = vec![F::zero(); size_of::<FieldArithCols<u8, FP>>()];
+                let cols: &mut FieldArithCols<F, FP> = row.as_mut_slice().borrow_mut();
+
+                let p_int = BigUint::from_slice(&event.p);
+                let q_int = BigUint::from_slice(&event.q);
+                cols.p_op_q.populate::<FP>(&p_int, &q_int, self.op);
+
+                let mut new_byte_lookup_events = Vec::new();
+                for i in 0..words_len {
+                    cols.q_access[i]
+                        .populate(event.q_memory_records[i], &mut new_byte_lookup_events);
+                }
+                for i in 0..words_len {
+                    cols.p_access[i]
+                        .populate(event.p_memory_records[i], &mut new_byte_lookup_events);
+                }
+
+                new_byte_lookup_events
+            })
+            .collect();
+
+        for byte_lookup_events in new_byte_lookup_events {
+            output.add_byte_lookup_events(byte_lookup_events);
+        }
+    }
+}
+
+impl<F, FP: FieldParameters> BaseAir<F> for FieldOpChip<FP> {
+    fn width(&self) -> usize {
+        size_of::<FieldArithCols<u8, FP>>()
+    }
+}
+
+impl<AB, FP: FieldParameters> Air<AB> for FieldOpChip<FP>
+where
+    AB: SP1AirBuilder,
+{
+    fn eval(&self, builder: &mut AB) {
+        let words_len = WORDS_FIELD_ELEMENT::<FP::NB_LIMBS>::USIZE;
+        let main = builder.main();
+        let row: &FieldArithCols<AB::Var, FP> = main.row_slice(0).borrow();
+        let next: &FieldArithCols<AB::Var, FP> = main.row_slice(1).borrow();
+
+        // The nonce of the first row is 0, and nonces increment by 1 for each subsequent real row.
+        builder.when_first_row().assert_zero(row.nonce);
+        builder
+            .when_transition()
+            .when(next.is_real)
+            .assert_eq(row.nonce + AB::F::one(), next.nonce);
+
+        let p: Limbs<_, FP::NB_LIMBS> = limbs_from_prev_access(&row.p_access[0..words_len]);
+        let q: Limbs<_, FP::NB_LIMBS> = limbs_from_prev_access(&row.q_access[0..words_len]);
+
+        row.p_op_q.eval::<AB, FP, _, _>(builder, &p, &q, self.op);
+
+        // Constraint self.p_access.value = [self.p_op_q.result]
+        // This is to ensure that p_access is updated with the new value.
+        for i in 0..FP::NB_LIMBS::USIZE {
+            builder
+                .when(row.is_real)
+                .assert_eq(row.p_op_q.result[i], row.p_access[i / 4].value()[i % 4]);
+        }
+
+        for i in 0..words_len {
+            builder.constraint_memory_access(
+                row.shard,
+                row.clk, // clk + 0 -> Memory
+                row.q_ptr + AB::F::from_canonical_u32(i as u32 * 4),
+                &row.q_access[i],
+                row.is_real,
+            );
+        }
+        for i in 0..words_len {
+            builder.constraint_memory_access(
+                row.shard,
+                row.clk + AB::F::from_canonical_u32(1), // The clk for p is moved by 1.
+                row.p_ptr + AB::F::from_canonical_u32(i as u32 * 4),
+                &row.p_access[i],
+                row.is_real,
+            );
+        }
+
+        // Fetch the syscall id for the (field, operation) pair.
+        let syscall_id_fe = AB::F::from_canonical_u32(FP::syscall_code(self.op).syscall_id());
+
+        builder.receive_syscall(
+            row.shard,
+            row.clk,
+            row.nonce,
+            syscall_id_fe,
+            row.p_ptr,
+            row.q_ptr,
+            row.is_real,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils;
+    use crate::utils::tests::{BLS12381_FP_ADD_ELF, BLS12381_FP_MUL_ELF, BLS12381_FP_SUB_ELF};
+    use crate::Program;
+
+    #[test]
+    fn test_bls12381_fp_add_simple() {
+        utils::setup_logger();
+        let program = Program::from(BLS12381_FP_ADD_ELF);
+        utils::run_test(program).unwrap();
+    }
+
+    #[test]
+    fn test_bls12381_fp_sub_simple() {
+        utils::setup_logger();
+        let program = Program::from(BLS12381_FP_SUB_ELF);
+        utils::run_test(program).unwrap();
+    }
+
+    #[test]
+    fn test_bls12381_fp_mul_simple() {
+        utils::setup_logger();
+        let program = Program::from(BLS12381_FP_MUL_ELF);
+        utils::run_test(program).unwrap();
+    }
+}
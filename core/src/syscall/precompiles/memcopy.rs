@@ -0,0 +1,300 @@
+//! A dedicated bulk memory-copy precompile. Guest programs that copy large contiguous buffers
+//! word-by-word would otherwise pay for each word as a RISC-V load/store pair going through the
+//! CPU table; proving the copy here instead lets `memcpy`-heavy workloads (serialization, hashing
+//! input prep) shed a large fraction of their CPU rows.
+
+use crate::air::MachineAir;
+use crate::air::SP1AirBuilder;
+use crate::bytes::ByteLookupEvent;
+use crate::memory::MemoryCols;
+use crate::memory::MemoryReadCols;
+use crate::memory::MemoryWriteCols;
+use crate::runtime::ExecutionRecord;
+use crate::runtime::MemoryReadRecord;
+use crate::runtime::MemoryWriteRecord;
+use crate::runtime::PrecompileEvent;
+use crate::runtime::Program;
+use crate::runtime::SyscallCode;
+use crate::syscall::precompiles::SyscallContext;
+use crate::utils::pad_vec_rows;
+use core::borrow::{Borrow, BorrowMut};
+use core::mem::size_of;
+use hybrid_array::typenum::Unsigned;
+use hybrid_array::{Array, ArrayLength};
+use p3_air::AirBuilder;
+use p3_air::{Air, BaseAir};
+use p3_field::AbstractField;
+use p3_field::PrimeField32;
+use p3_matrix::dense::RowMajorMatrix;
+use p3_matrix::MatrixRowSlices;
+use p3_maybe_rayon::prelude::IntoParallelRefIterator;
+use p3_maybe_rayon::prelude::ParallelIterator;
+use serde::Deserialize;
+use serde::Serialize;
+use std::marker::PhantomData;
+use tracing::instrument;
+use wp1_derive::AlignedBorrow;
+
+/// A set of columns to prove a bulk move of up to `N` words from `src_ptr` to `dst_ptr`. `N` is a
+/// compile-time upper bound (so fixed widths matching `WORDS_FIELD_ELEMENT` or point sizes can be
+/// instantiated without a variable-length loop in the AIR), while `len` is the actual, runtime
+/// number of words copied by this particular event: `word_is_active[i]` is `1` for `i < len` and
+/// `0` otherwise, so `len` is implicitly bounded to `0..=N` by construction rather than needing a
+/// separate range check.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct MemCopyCols<T, N: ArrayLength> {
+    pub is_real: T,
+    /// This row's index within the shard's trace for this chip, constrained to match the
+    /// `nonce` sent to `receive_syscall` so no two rows can collide in the cross-table argument.
+    pub nonce: T,
+    pub shard: T,
+    pub clk: T,
+    pub src_ptr: T,
+    pub dst_ptr: T,
+    pub len: T,
+    pub word_is_active: Array<T, N>,
+    pub src_access: Array<MemoryReadCols<T>, N>,
+    pub dst_access: Array<MemoryWriteCols<T>, N>,
+}
+
+/// A bulk memory-copy event: `len` (`<= N`) words read from `src_ptr` and written to `dst_ptr`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemCopyEvent<N: ArrayLength> {
+    pub shard: u32,
+    pub clk: u32,
+    /// This event's index within its shard's per-syscall vector, stamped by
+    /// [`ExecutionRecord::assign_nonces`] once the shard is finalized. Defaults to `0` so older
+    /// serialized records still deserialize.
+    #[serde(default)]
+    pub nonce: u32,
+    pub src_ptr: u32,
+    pub dst_ptr: u32,
+    pub len: u32,
+    #[serde(with = "crate::utils::array_serde::ArraySerde")]
+    pub src_memory_records: Array<MemoryReadRecord, N>,
+    #[serde(with = "crate::utils::array_serde::ArraySerde")]
+    pub dst_memory_records: Array<MemoryWriteRecord, N>,
+}
+
+/// Reads `len` words (`len <= N::USIZE`) at `src_ptr` and writes them to `dst_ptr`, clk-bumped
+/// between the read and write exactly like the field-arithmetic precompiles handle `p`/`q`
+/// aliasing. Slots `len..N` are padded with default records, whose memory accesses are gated off
+/// by `word_is_active`.
+pub fn create_memcpy_event<N: ArrayLength>(
+    rt: &mut SyscallContext<'_>,
+    src_ptr: u32,
+    dst_ptr: u32,
+    len: usize,
+) -> MemCopyEvent<N> {
+    let start_clk = rt.clk;
+    assert!(src_ptr % 4 == 0);
+    assert!(dst_ptr % 4 == 0);
+    assert!(len <= N::USIZE);
+
+    let (mut src_memory_records_vec, src_vec) = rt.mr_slice(src_ptr, len);
+    rt.record_mut()
+        .update_local_memory_access_reads(src_ptr, &src_memory_records_vec);
+    src_memory_records_vec.resize(N::USIZE, MemoryReadRecord::default());
+    let src_memory_records = (&src_memory_records_vec[..]).try_into().unwrap();
+
+    rt.clk += 1;
+    let mut dst_memory_records_vec = rt.mw_slice(dst_ptr, &src_vec);
+    rt.record_mut()
+        .update_local_memory_access_writes(dst_ptr, &dst_memory_records_vec);
+    dst_memory_records_vec.resize(N::USIZE, MemoryWriteRecord::default());
+    let dst_memory_records = (&dst_memory_records_vec[..]).try_into().unwrap();
+
+    MemCopyEvent {
+        shard: rt.current_shard(),
+        clk: start_clk,
+        nonce: 0,
+        src_ptr,
+        dst_ptr,
+        len: len as u32,
+        src_memory_records,
+        dst_memory_records,
+    }
+}
+
+/// Ties a compile-time copy width `N` to the syscall that emits it and the events
+/// `ExecutionRecord::precompile_events` stores under that syscall. Additional fixed widths
+/// (curve-point-sized copies, etc.) get their own `SyscallCode` and impl the same way
+/// `FieldType`/`FieldOperation` pairs do for `WithFieldArithmetic`.
+pub trait MemCopyWidth: ArrayLength {
+    const SYSCALL_CODE: SyscallCode;
+
+    fn wrap_event(event: MemCopyEvent<Self>) -> PrecompileEvent;
+
+    fn memcopy_events(record: &ExecutionRecord) -> Vec<&MemCopyEvent<Self>>;
+}
+
+#[derive(Default)]
+pub struct MemCopyChip<N> {
+    _marker: PhantomData<N>,
+}
+
+impl<N> MemCopyChip<N> {
+    pub fn new() -> Self {
+        Self {
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<F: PrimeField32, N: MemCopyWidth + Send + Sync> MachineAir<F> for MemCopyChip<N> {
+    type Record = ExecutionRecord;
+    type Program = Program;
+
+    fn name(&self) -> String {
+        format!("MemCopy{}", N::USIZE)
+    }
+
+    #[instrument(name = "generate memcopy trace", level = "debug", skip_all)]
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        output: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let events = N::memcopy_events(input);
+
+        let (mut rows, new_byte_lookup_events): (Vec<_>, Vec<Vec<ByteLookupEvent>>) = events
+            .par_iter()
+            .map(|event| {
+                let words_len = N::USIZE;
+                let mut row = vec![F::zero(); size_of::<MemCopyCols<u8, N>>()];
+                let cols: &mut MemCopyCols<F, N> = row.as_mut_slice().borrow_mut();
+
+                cols.is_real = F::one();
+                cols.nonce = F::from_canonical_u32(event.nonce);
+                cols.shard = F::from_canonical_u32(event.shard);
+                cols.clk = F::from_canonical_u32(event.clk);
+                cols.src_ptr = F::from_canonical_u32(event.src_ptr);
+                cols.dst_ptr = F::from_canonical_u32(event.dst_ptr);
+                cols.len = F::from_canonical_u32(event.len);
+
+                let mut new_byte_lookup_events = Vec::new();
+                for i in 0..words_len {
+                    cols.word_is_active[i] = F::from_bool(i < event.len as usize);
+                    cols.src_access[i]
+                        .populate(event.src_memory_records[i], &mut new_byte_lookup_events);
+                }
+                for i in 0..words_len {
+                    cols.dst_access[i]
+                        .populate(event.dst_memory_records[i], &mut new_byte_lookup_events);
+                }
+
+                (row, new_byte_lookup_events)
+            })
+            .unzip();
+
+        for byte_lookup_events in new_byte_lookup_events {
+            output.add_byte_lookup_events(byte_lookup_events);
+        }
+
+        pad_vec_rows(&mut rows, || {
+            vec![F::zero(); size_of::<MemCopyCols<u8, N>>()]
+        });
+
+        RowMajorMatrix::new(
+            rows.into_iter().flatten().collect::<Vec<_>>(),
+            size_of::<MemCopyCols<u8, N>>(),
+        )
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        !N::memcopy_events(shard).is_empty()
+    }
+}
+
+impl<F, N: ArrayLength> BaseAir<F> for MemCopyChip<N> {
+    fn width(&self) -> usize {
+        size_of::<MemCopyCols<u8, N>>()
+    }
+}
+
+impl<AB, N: ArrayLength> Air<AB> for MemCopyChip<N>
+where
+    AB: SP1AirBuilder,
+{
+    fn eval(&self, builder: &mut AB) {
+        let words_len = N::USIZE;
+        let main = builder.main();
+        let row: &MemCopyCols<AB::Var, N> = main.row_slice(0).borrow();
+        let next: &MemCopyCols<AB::Var, N> = main.row_slice(1).borrow();
+
+        // The nonce of the first row is 0, and nonces increment by 1 for each subsequent real row.
+        builder.when_first_row().assert_zero(row.nonce);
+        builder
+            .when_transition()
+            .when(next.is_real)
+            .assert_eq(row.nonce + AB::F::one(), next.nonce);
+
+        // `word_is_active` is a prefix of ones: each entry is boolean, and once it drops to zero
+        // it stays zero, so the running sum is exactly `len` and `len` can never exceed `N`.
+        let mut active_sum = AB::Expr::zero();
+        for i in 0..words_len {
+            builder.assert_bool(row.word_is_active[i]);
+            if i + 1 < words_len {
+                // If word i+1 is active, word i must be active too: active[i+1] * (1 - active[i]) == 0.
+                let active_next: AB::Expr = row.word_is_active[i + 1].into();
+                let active_cur: AB::Expr = row.word_is_active[i].into();
+                builder.assert_zero(active_next * (AB::Expr::one() - active_cur));
+            }
+            active_sum += row.word_is_active[i].into();
+        }
+        builder.when(row.is_real).assert_eq(row.len, active_sum);
+
+        for i in 0..words_len {
+            let is_active: AB::Expr = row.is_real.into() * row.word_is_active[i].into();
+            builder
+                .when(is_active)
+                .assert_all_eq(row.dst_access[i].value(), row.src_access[i].prev_value());
+        }
+
+        for i in 0..words_len {
+            let is_active: AB::Expr = row.is_real.into() * row.word_is_active[i].into();
+            builder.constraint_memory_access(
+                row.shard,
+                row.clk,
+                row.src_ptr + AB::F::from_canonical_u32(i as u32 * 4),
+                &row.src_access[i],
+                is_active,
+            );
+        }
+        for i in 0..words_len {
+            let is_active: AB::Expr = row.is_real.into() * row.word_is_active[i].into();
+            builder.constraint_memory_access(
+                row.shard,
+                row.clk + AB::F::from_canonical_u32(1),
+                row.dst_ptr + AB::F::from_canonical_u32(i as u32 * 4),
+                &row.dst_access[i],
+                is_active,
+            );
+        }
+
+        builder.receive_syscall(
+            row.shard,
+            row.clk,
+            row.nonce,
+            AB::F::from_canonical_u32(SyscallCode::MEMCOPY.syscall_id()),
+            row.src_ptr,
+            row.dst_ptr,
+            row.is_real,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils;
+    use crate::utils::tests::MEMCOPY_ELF;
+    use crate::Program;
+
+    #[test]
+    fn test_memcopy_simple() {
+        utils::setup_logger();
+        let program = Program::from(MEMCOPY_ELF);
+        utils::run_test(program).unwrap();
+    }
+}
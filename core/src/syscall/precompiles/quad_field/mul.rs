@@ -0,0 +1,15 @@
+use crate::operations::field::field_op::FieldOperation;
+use crate::syscall::precompiles::quad_field::op::{create_fp2_arith_event, Fp2ArithEvent};
+use crate::syscall::precompiles::SyscallContext;
+use crate::utils::ec::field::FieldParameters;
+
+/// Fp2 multiplication event.
+pub type QuadFieldMulEvent<FP> = Fp2ArithEvent<FP>;
+
+pub fn create_fp2_mul_event<FP: FieldParameters>(
+    rt: &mut SyscallContext<'_>,
+    arg1: u32,
+    arg2: u32,
+) -> QuadFieldMulEvent<FP> {
+    create_fp2_arith_event::<FP>(rt, arg1, arg2, FieldOperation::Mul)
+}
@@ -0,0 +1,510 @@
+use crate::air::MachineAir;
+use crate::air::SP1AirBuilder;
+use crate::bytes::ByteLookupEvent;
+use crate::memory::MemoryCols;
+use crate::memory::MemoryReadCols;
+use crate::memory::MemoryWriteCols;
+use crate::operations::field::field_op::FieldOpCols;
+use crate::operations::field::field_op::FieldOperation;
+use crate::operations::field::params::Limbs;
+use crate::operations::field::params::WORDS_FIELD_ELEMENT;
+use crate::runtime::ExecutionRecord;
+use crate::runtime::MemoryReadRecord;
+use crate::runtime::MemoryWriteRecord;
+use crate::runtime::Program;
+use crate::runtime::SyscallCode;
+use crate::syscall::precompiles::SyscallContext;
+use crate::utils::bytes_to_words_le;
+use crate::utils::ec::field::FieldParameters;
+use crate::utils::ec::field::WithQuadFieldArithmetic;
+use crate::utils::limbs_from_prev_access;
+use crate::utils::pad_vec_rows;
+use core::borrow::{Borrow, BorrowMut};
+use core::mem::size_of;
+use hybrid_array::typenum::Unsigned;
+use hybrid_array::Array;
+use num::BigUint;
+use num::Zero;
+use p3_air::AirBuilder;
+use p3_air::{Air, BaseAir};
+use p3_field::AbstractField;
+use p3_field::PrimeField32;
+use p3_matrix::dense::RowMajorMatrix;
+use p3_matrix::MatrixRowSlices;
+use p3_maybe_rayon::prelude::IntoParallelRefIterator;
+use p3_maybe_rayon::prelude::ParallelIterator;
+use serde::Deserialize;
+use serde::Serialize;
+use std::marker::PhantomData;
+use tracing::instrument;
+use wp1_derive::AlignedBorrow;
+
+/// A set of columns to compute a binary Fp2 operation `a OP b` in the tower field
+/// `Fp[i]/(i^2 - beta)`, where `a = (a0, a1)` and `b = (b0, b1)` are each two consecutive
+/// `WORDS_FIELD_ELEMENT` blocks. Mirrors [`crate::syscall::precompiles::field::op::FieldArithCols`]
+/// but over the quadratic extension.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct Fp2ArithCols<T, FP: FieldParameters> {
+    pub is_real: T,
+    /// This row's index within the shard's trace for this chip, constrained to match the
+    /// `nonce` sent to `receive_syscall` so no two rows can collide in the cross-table argument.
+    pub nonce: T,
+    pub shard: T,
+    pub clk: T,
+    pub a_ptr: T,
+    pub b_ptr: T,
+    pub a0_access: Array<MemoryWriteCols<T>, WORDS_FIELD_ELEMENT<FP::NB_LIMBS>>,
+    pub a1_access: Array<MemoryWriteCols<T>, WORDS_FIELD_ELEMENT<FP::NB_LIMBS>>,
+    pub b0_access: Array<MemoryReadCols<T>, WORDS_FIELD_ELEMENT<FP::NB_LIMBS>>,
+    pub b1_access: Array<MemoryReadCols<T>, WORDS_FIELD_ELEMENT<FP::NB_LIMBS>>,
+    /// `a0 * b0` (Mul only).
+    pub(crate) v0: FieldOpCols<T, FP::NB_LIMBS>,
+    /// `a1 * b1` (Mul only).
+    pub(crate) v1: FieldOpCols<T, FP::NB_LIMBS>,
+    /// `beta * v1` (Mul only); beta = -1 for BLS12-381, so this is `0 - v1`.
+    pub(crate) beta_v1: FieldOpCols<T, FP::NB_LIMBS>,
+    /// `(a0 + a1) * (b0 + b1)` (Mul only, Karatsuba cross term).
+    pub(crate) a_sum: FieldOpCols<T, FP::NB_LIMBS>,
+    pub(crate) b_sum: FieldOpCols<T, FP::NB_LIMBS>,
+    pub(crate) cross: FieldOpCols<T, FP::NB_LIMBS>,
+    /// First limb of the result: `a0 OP b0` (Add/Sub) or `v0 + beta*v1` (Mul).
+    pub(crate) c0: FieldOpCols<T, FP::NB_LIMBS>,
+    /// Second limb of the result: `a1 OP b1` (Add/Sub) or `cross - v0 - v1` (Mul).
+    pub(crate) c1: FieldOpCols<T, FP::NB_LIMBS>,
+    pub(crate) c1_sub_v0: FieldOpCols<T, FP::NB_LIMBS>,
+}
+
+/// A binary Fp2 arithmetic event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Fp2ArithEvent<FP: FieldParameters> {
+    pub shard: u32,
+    pub clk: u32,
+    /// This event's index within its shard's per-syscall vector, stamped by
+    /// [`ExecutionRecord::assign_nonces`](crate::runtime::ExecutionRecord::assign_nonces) once
+    /// the shard is finalized. Defaults to `0` so older serialized records still deserialize.
+    #[serde(default)]
+    pub nonce: u32,
+    pub a_ptr: u32,
+    #[serde(with = "crate::utils::array_serde::ArraySerde")]
+    pub a0: Array<u32, WORDS_FIELD_ELEMENT<FP::NB_LIMBS>>,
+    #[serde(with = "crate::utils::array_serde::ArraySerde")]
+    pub a1: Array<u32, WORDS_FIELD_ELEMENT<FP::NB_LIMBS>>,
+    pub b_ptr: u32,
+    #[serde(with = "crate::utils::array_serde::ArraySerde")]
+    pub b0: Array<u32, WORDS_FIELD_ELEMENT<FP::NB_LIMBS>>,
+    #[serde(with = "crate::utils::array_serde::ArraySerde")]
+    pub b1: Array<u32, WORDS_FIELD_ELEMENT<FP::NB_LIMBS>>,
+    #[serde(with = "crate::utils::array_serde::ArraySerde")]
+    pub a0_memory_records: Array<MemoryWriteRecord, WORDS_FIELD_ELEMENT<FP::NB_LIMBS>>,
+    #[serde(with = "crate::utils::array_serde::ArraySerde")]
+    pub a1_memory_records: Array<MemoryWriteRecord, WORDS_FIELD_ELEMENT<FP::NB_LIMBS>>,
+    #[serde(with = "crate::utils::array_serde::ArraySerde")]
+    pub b0_memory_records: Array<MemoryReadRecord, WORDS_FIELD_ELEMENT<FP::NB_LIMBS>>,
+    #[serde(with = "crate::utils::array_serde::ArraySerde")]
+    pub b1_memory_records: Array<MemoryReadRecord, WORDS_FIELD_ELEMENT<FP::NB_LIMBS>>,
+}
+
+/// Reads `a = (a0, a1)` at `a_ptr` and `b = (b0, b1)` at `b_ptr` (each element two consecutive
+/// `WORDS_FIELD_ELEMENT` blocks) and writes `a OP b` back to `a_ptr`, clk-bumped to handle
+/// aliasing exactly like `create_fp_arith_event`.
+pub fn create_fp2_arith_event<FP: FieldParameters>(
+    rt: &mut SyscallContext<'_>,
+    arg1: u32,
+    arg2: u32,
+    op: FieldOperation,
+) -> Fp2ArithEvent<FP> {
+    let start_clk = rt.clk;
+    let a_ptr = arg1;
+    let b_ptr = arg2;
+    assert!(a_ptr % 4 == 0);
+    assert!(b_ptr % 4 == 0);
+
+    let words_len = WORDS_FIELD_ELEMENT::<FP::NB_LIMBS>::USIZE;
+
+    let (b0_memory_records_vec, b0_vec) = rt.mr_slice(b_ptr, words_len);
+    rt.record_mut()
+        .update_local_memory_access_reads(b_ptr, &b0_memory_records_vec);
+    let (b1_memory_records_vec, b1_vec) = rt.mr_slice(b_ptr + 4 * words_len as u32, words_len);
+    rt.record_mut().update_local_memory_access_reads(
+        b_ptr + 4 * words_len as u32,
+        &b1_memory_records_vec,
+    );
+    let b0_memory_records = (&b0_memory_records_vec[..]).try_into().unwrap();
+    let b1_memory_records = (&b1_memory_records_vec[..]).try_into().unwrap();
+    let b0: Array<u32, _> = (&b0_vec[..]).try_into().unwrap();
+    let b1: Array<u32, _> = (&b1_vec[..]).try_into().unwrap();
+    let b0_int = BigUint::from_slice(&b0);
+    let b1_int = BigUint::from_slice(&b1);
+
+    let a0: Array<u32, _> = (&rt.slice_unsafe(a_ptr, words_len)[..]).try_into().unwrap();
+    let a1: Array<u32, _> = (&rt.slice_unsafe(a_ptr + 4 * words_len as u32, words_len)[..])
+        .try_into()
+        .unwrap();
+    let a0_int = BigUint::from_slice(&a0);
+    let a1_int = BigUint::from_slice(&a1);
+
+    let modulus = FP::modulus();
+    let (c0_int, c1_int) = match op {
+        FieldOperation::Add => (
+            (&a0_int + &b0_int) % &modulus,
+            (&a1_int + &b1_int) % &modulus,
+        ),
+        FieldOperation::Sub => (
+            (&modulus + &a0_int - &b0_int) % &modulus,
+            (&modulus + &a1_int - &b1_int) % &modulus,
+        ),
+        // (a0 + a1*i)(b0 + b1*i) = (a0*b0 - a1*b1) + (a0*b1 + a1*b0)*i, since beta = -1.
+        FieldOperation::Mul => {
+            let v0 = (&a0_int * &b0_int) % &modulus;
+            let v1 = (&a1_int * &b1_int) % &modulus;
+            let a_sum = (&a0_int + &a1_int) % &modulus;
+            let b_sum = (&b0_int + &b1_int) % &modulus;
+            let cross = (&a_sum * &b_sum) % &modulus;
+            let c0 = (&modulus + &v0 - &v1) % &modulus;
+            let c1 = (&modulus + &modulus + &cross - &v0 - &v1) % &modulus;
+            (c0, c1)
+        }
+        _ => panic!("Fp2OpChip only supports Add/Sub/Mul"),
+    };
+
+    // When we write to a, we want the clk to be incremented because a and b could alias.
+    rt.clk += 1;
+    let c0_bytes = FP::to_limbs(&c0_int);
+    let c1_bytes = FP::to_limbs(&c1_int);
+    let c0_words = bytes_to_words_le::<WORDS_FIELD_ELEMENT<FP::NB_LIMBS>>(&c0_bytes);
+    let c1_words = bytes_to_words_le::<WORDS_FIELD_ELEMENT<FP::NB_LIMBS>>(&c1_bytes);
+    let a0_memory_records_vec = rt.mw_slice(a_ptr, &c0_words.into());
+    rt.record_mut()
+        .update_local_memory_access_writes(a_ptr, &a0_memory_records_vec);
+    let a0_memory_records = (&a0_memory_records_vec[..]).try_into().unwrap();
+    let a1_memory_records_vec = rt.mw_slice(a_ptr + 4 * words_len as u32, &c1_words.into());
+    rt.record_mut().update_local_memory_access_writes(
+        a_ptr + 4 * words_len as u32,
+        &a1_memory_records_vec,
+    );
+    let a1_memory_records = (&a1_memory_records_vec[..]).try_into().unwrap();
+
+    Fp2ArithEvent {
+        shard: rt.current_shard(),
+        clk: start_clk,
+        nonce: 0,
+        a_ptr,
+        a0,
+        a1,
+        b_ptr,
+        b0,
+        b1,
+        a0_memory_records,
+        a1_memory_records,
+        b0_memory_records,
+        b1_memory_records,
+    }
+}
+
+/// A configurable Fp2 arithmetic precompile proving `a = a OP b` in `Fp[i]/(i^2 - beta)`.
+pub struct Fp2OpChip<FP: FieldParameters> {
+    op: FieldOperation,
+    _marker: PhantomData<FP>,
+}
+
+impl<FP: FieldParameters> Fp2OpChip<FP> {
+    pub fn new(op: FieldOperation) -> Self {
+        assert!(matches!(
+            op,
+            FieldOperation::Add | FieldOperation::Sub | FieldOperation::Mul
+        ));
+        Self {
+            op,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<F: PrimeField32, FP: FieldParameters + WithQuadFieldArithmetic> MachineAir<F>
+    for Fp2OpChip<FP>
+{
+    type Record = ExecutionRecord;
+    type Program = Program;
+
+    fn name(&self) -> String {
+        format!("{:?}Fp2{:?}", FP::FIELD_TYPE, self.op)
+    }
+
+    #[instrument(name = "generate fp2 op trace", level = "debug", skip_all)]
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        output: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let events = match self.op {
+            FieldOperation::Add => FP::fp2_add_events(input),
+            FieldOperation::Sub => FP::fp2_sub_events(input),
+            FieldOperation::Mul => FP::fp2_mul_events(input),
+            _ => unreachable!(),
+        };
+
+        let (mut rows, new_byte_lookup_events): (Vec<_>, Vec<Vec<ByteLookupEvent>>) = events
+            .par_iter()
+            .map(|event| {
+                let words_len = WORDS_FIELD_ELEMENT::<FP::NB_LIMBS>::USIZE;
+                let mut row = vec![F::zero(); size_of::<Fp2ArithCols<u8, FP>>()];
+                let cols: &mut Fp2ArithCols<F, FP> = row.as_mut_slice().borrow_mut();
+
+                cols.is_real = F::one();
+                cols.nonce = F::from_canonical_u32(event.nonce);
+                cols.shard = F::from_canonical_u32(event.shard);
+                cols.clk = F::from_canonical_u32(event.clk);
+                cols.a_ptr = F::from_canonical_u32(event.a_ptr);
+                cols.b_ptr = F::from_canonical_u32(event.b_ptr);
+
+                let a0 = BigUint::from_slice(&event.a0);
+                let a1 = BigUint::from_slice(&event.a1);
+                let b0 = BigUint::from_slice(&event.b0);
+                let b1 = BigUint::from_slice(&event.b1);
+
+                Self::populate_field_ops(cols, self.op, &a0, &a1, &b0, &b1);
+
+                let mut new_byte_lookup_events = Vec::new();
+                for i in 0..words_len {
+                    cols.b0_access[i]
+                        .populate(event.b0_memory_records[i], &mut new_byte_lookup_events);
+                }
+                for i in 0..words_len {
+                    cols.b1_access[i]
+                        .populate(event.b1_memory_records[i], &mut new_byte_lookup_events);
+                }
+                for i in 0..words_len {
+                    cols.a0_access[i]
+                        .populate(event.a0_memory_records[i], &mut new_byte_lookup_events);
+                }
+                for i in 0..words_len {
+                    cols.a1_access[i]
+                        .populate(event.a1_memory_records[i], &mut new_byte_lookup_events);
+                }
+
+                (row, new_byte_lookup_events)
+            })
+            .unzip();
+
+        for byte_lookup_events in new_byte_lookup_events {
+            output.add_byte_lookup_events(byte_lookup_events);
+        }
+
+        pad_vec_rows(&mut rows, || {
+            let mut row = vec![F::zero(); size_of::<Fp2ArithCols<u8, FP>>()];
+            let cols: &mut Fp2ArithCols<F, FP> = row.as_mut_slice().borrow_mut();
+            let zero = BigUint::zero();
+            Self::populate_field_ops(cols, self.op, &zero, &zero, &zero, &zero);
+            row
+        });
+
+        RowMajorMatrix::new(
+            rows.into_iter().flatten().collect::<Vec<_>>(),
+            size_of::<Fp2ArithCols<u8, FP>>(),
+        )
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        !shard
+            .get_precompile_events(FP::fp2_syscall_code(self.op))
+            .is_empty()
+    }
+}
+
+impl<FP: FieldParameters> Fp2OpChip<FP> {
+    /// Populates the `FieldOpCols` for the configured operation, using Karatsuba for Mul:
+    /// `v0 = a0*b0`, `v1 = a1*b1`, `c0 = v0 + beta*v1`, `c1 = (a0+a1)*(b0+b1) - v0 - v1`.
+    fn populate_field_ops<F: PrimeField32>(
+        cols: &mut Fp2ArithCols<F, FP>,
+        op: FieldOperation,
+        a0: &BigUint,
+        a1: &BigUint,
+        b0: &BigUint,
+        b1: &BigUint,
+    ) {
+        match op {
+            FieldOperation::Add | FieldOperation::Sub => {
+                cols.c0.populate::<FP>(a0, b0, op);
+                cols.c1.populate::<FP>(a1, b1, op);
+            }
+            FieldOperation::Mul => {
+                let v0 = cols.v0.populate::<FP>(a0, b0, FieldOperation::Mul);
+                let v1 = cols.v1.populate::<FP>(a1, b1, FieldOperation::Mul);
+                // beta = -1 for BLS12-381: beta * v1 == 0 - v1.
+                let beta_v1 = cols
+                    .beta_v1
+                    .populate::<FP>(&BigUint::zero(), &v1, FieldOperation::Sub);
+                let a_sum = cols.a_sum.populate::<FP>(a0, a1, FieldOperation::Add);
+                let b_sum = cols.b_sum.populate::<FP>(b0, b1, FieldOperation::Add);
+                let cross = cols
+                    .cross
+                    .populate::<FP>(&a_sum, &b_sum, FieldOperation::Mul);
+                cols.c0
+                    .populate::<FP>(&v0, &beta_v1, FieldOperation::Add);
+                let cross_sub_v0 = cols
+                    .c1_sub_v0
+                    .populate::<FP>(&cross, &v0, FieldOperation::Sub);
+                cols.c1
+                    .populate::<FP>(&cross_sub_v0, &v1, FieldOperation::Sub);
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl<F, FP: FieldParameters> BaseAir<F> for Fp2OpChip<FP> {
+    fn width(&self) -> usize {
+        size_of::<Fp2ArithCols<u8, FP>>()
+    }
+}
+
+impl<AB, FP: FieldParameters> Air<AB> for Fp2OpChip<FP>
+where
+    AB: SP1AirBuilder,
+{
+    fn eval(&self, builder: &mut AB) {
+        let words_len = WORDS_FIELD_ELEMENT::<FP::NB_LIMBS>::USIZE;
+        let main = builder.main();
+        let row: &Fp2ArithCols<AB::Var, FP> = main.row_slice(0).borrow();
+        let next: &Fp2ArithCols<AB::Var, FP> = main.row_slice(1).borrow();
+
+        // The nonce of the first row is 0, and nonces increment by 1 for each subsequent real row.
+        builder.when_first_row().assert_zero(row.nonce);
+        builder
+            .when_transition()
+            .when(next.is_real)
+            .assert_eq(row.nonce + AB::F::one(), next.nonce);
+
+        let a0: Limbs<_, FP::NB_LIMBS> = limbs_from_prev_access(&row.a0_access[0..words_len]);
+        let a1: Limbs<_, FP::NB_LIMBS> = limbs_from_prev_access(&row.a1_access[0..words_len]);
+        let b0: Limbs<_, FP::NB_LIMBS> = limbs_from_prev_access(&row.b0_access[0..words_len]);
+        let b1: Limbs<_, FP::NB_LIMBS> = limbs_from_prev_access(&row.b1_access[0..words_len]);
+
+        match self.op {
+            FieldOperation::Add | FieldOperation::Sub => {
+                row.c0.eval::<AB, FP, _, _>(builder, &a0, &b0, self.op);
+                row.c1.eval::<AB, FP, _, _>(builder, &a1, &b1, self.op);
+            }
+            FieldOperation::Mul => {
+                row.v0
+                    .eval::<AB, FP, _, _>(builder, &a0, &b0, FieldOperation::Mul);
+                row.v1
+                    .eval::<AB, FP, _, _>(builder, &a1, &b1, FieldOperation::Mul);
+                row.beta_v1.eval::<AB, FP, _, _>(
+                    builder,
+                    &[AB::Expr::zero()].iter(),
+                    &row.v1.result,
+                    FieldOperation::Sub,
+                );
+                row.a_sum
+                    .eval::<AB, FP, _, _>(builder, &a0, &a1, FieldOperation::Add);
+                row.b_sum
+                    .eval::<AB, FP, _, _>(builder, &b0, &b1, FieldOperation::Add);
+                row.cross.eval::<AB, FP, _, _>(
+                    builder,
+                    &row.a_sum.result,
+                    &row.b_sum.result,
+                    FieldOperation::Mul,
+                );
+                row.c0.eval::<AB, FP, _, _>(
+                    builder,
+                    &row.v0.result,
+                    &row.beta_v1.result,
+                    FieldOperation::Add,
+                );
+                row.c1_sub_v0.eval::<AB, FP, _, _>(
+                    builder,
+                    &row.cross.result,
+                    &row.v0.result,
+                    FieldOperation::Sub,
+                );
+                row.c1.eval::<AB, FP, _, _>(
+                    builder,
+                    &row.c1_sub_v0.result,
+                    &row.v1.result,
+                    FieldOperation::Sub,
+                );
+            }
+            _ => unreachable!(),
+        }
+
+        for i in 0..FP::NB_LIMBS::USIZE {
+            builder
+                .when(row.is_real)
+                .assert_eq(row.c0.result[i], row.a0_access[i / 4].value()[i % 4]);
+            builder
+                .when(row.is_real)
+                .assert_eq(row.c1.result[i], row.a1_access[i / 4].value()[i % 4]);
+        }
+
+        for i in 0..words_len {
+            builder.constraint_memory_access(
+                row.shard,
+                row.clk,
+                row.b_ptr + AB::F::from_canonical_u32(i as u32 * 4),
+                &row.b0_access[i],
+                row.is_real,
+            );
+        }
+        for i in 0..words_len {
+            builder.constraint_memory_access(
+                row.shard,
+                row.clk,
+                row.b_ptr + AB::F::from_canonical_u32((i + words_len) as u32 * 4),
+                &row.b1_access[i],
+                row.is_real,
+            );
+        }
+        for i in 0..words_len {
+            builder.constraint_memory_access(
+                row.shard,
+                row.clk + AB::F::from_canonical_u32(1),
+                row.a_ptr + AB::F::from_canonical_u32(i as u32 * 4),
+                &row.a0_access[i],
+                row.is_real,
+            );
+        }
+        for i in 0..words_len {
+            builder.constraint_memory_access(
+                row.shard,
+                row.clk + AB::F::from_canonical_u32(1),
+                row.a_ptr + AB::F::from_canonical_u32((i + words_len) as u32 * 4),
+                &row.a1_access[i],
+                row.is_real,
+            );
+        }
+
+        let syscall_id_fe = AB::F::from_canonical_u32(FP::fp2_syscall_code(self.op).syscall_id());
+        builder.receive_syscall(
+            row.shard,
+            row.clk,
+            row.nonce,
+            syscall_id_fe,
+            row.a_ptr,
+            row.b_ptr,
+            row.is_real,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils;
+    use crate::utils::tests::{BLS12381_FP2_ADDSUB_ELF, BLS12381_FP2_MUL_ELF};
+    use crate::Program;
+
+    #[test]
+    fn test_bls12381_fp2_addsub_simple() {
+        utils::setup_logger();
+        let program = Program::from(BLS12381_FP2_ADDSUB_ELF);
+        utils::run_test(program).unwrap();
+    }
+
+    #[test]
+    fn test_bls12381_fp2_mul_simple() {
+        utils::setup_logger();
+        let program = Program::from(BLS12381_FP2_MUL_ELF);
+        utils::run_test(program).unwrap();
+    }
+}
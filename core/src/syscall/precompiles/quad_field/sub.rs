@@ -0,0 +1,15 @@
+use crate::operations::field::field_op::FieldOperation;
+use crate::syscall::precompiles::quad_field::op::{create_fp2_arith_event, Fp2ArithEvent};
+use crate::syscall::precompiles::SyscallContext;
+use crate::utils::ec::field::FieldParameters;
+
+/// Fp2 subtraction event.
+pub type QuadFieldSubEvent<FP> = Fp2ArithEvent<FP>;
+
+pub fn create_fp2_sub_event<FP: FieldParameters>(
+    rt: &mut SyscallContext<'_>,
+    arg1: u32,
+    arg2: u32,
+) -> QuadFieldSubEvent<FP> {
+    create_fp2_arith_event::<FP>(rt, arg1, arg2, FieldOperation::Sub)
+}
@@ -0,0 +1,739 @@
+//! BLS12-381 G2 point decompression. `WeierstrassDecompressChip` only handles curves whose
+//! coordinates live in the base field; G2's `x`/`y` are instead elements of the quadratic
+//! extension `Fp2 = Fp[u]/(u^2+1)`, and the curve equation uses the sextic twist constant
+//! `b' = 4*(1+u)`. The square root needed to recover `y` from `x^3+b'` uses that BLS12-381's
+//! base prime satisfies `p = 3 mod 4`: witness `a1 = a^((p-3)/4)`, then `x0 = a1*a` and
+//! `alpha = a1*x0 (== a1^2*a)`; if `alpha == -1` the root is `u*x0`, otherwise it is `b*x0`
+//! where `b = (1+alpha)^((p-1)/2)`. As with the existing single-field `FieldSqrtCols`, the
+//! expensive exponentiations happen out of circuit (see `bls12381_fp2_sqrt`); the AIR only
+//! re-derives `x0`/`alpha` from the witnessed `a1`, checks `b^2 == 1+alpha`, and constrains the
+//! final `result^2 == x^3+b'`.
+//!
+//! This chip picks between `y` and `-y` by `y0`'s parity, except when `y0 == 0`, where the two
+//! candidates share the same (even) `y0` and `y1`'s parity decides instead -- see
+//! [`Bls12381G2DecompressCols::y_least_bits`].
+
+use core::borrow::{Borrow, BorrowMut};
+use core::mem::size_of;
+
+use hybrid_array::typenum::Unsigned;
+use hybrid_array::Array;
+use num::BigUint;
+use num::Zero;
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::AbstractField;
+use p3_field::PrimeField32;
+use p3_matrix::dense::RowMajorMatrix;
+use p3_matrix::MatrixRowSlices;
+use p3_maybe_rayon::prelude::IntoParallelRefIterator;
+use p3_maybe_rayon::prelude::ParallelIterator;
+use serde::Deserialize;
+use serde::Serialize;
+use wp1_derive::AlignedBorrow;
+
+use crate::air::MachineAir;
+use crate::air::SP1AirBuilder;
+use crate::memory::MemoryReadCols;
+use crate::memory::MemoryReadWriteCols;
+use crate::operations::field::field_op::FieldOpCols;
+use crate::operations::field::field_op::FieldOperation;
+use crate::operations::field::params::{Limbs, WORDS_FIELD_ELEMENT};
+use crate::runtime::ExecutionRecord;
+use crate::runtime::MemoryReadRecord;
+use crate::runtime::MemoryWriteRecord;
+use crate::runtime::PrecompileEvent;
+use crate::runtime::Program;
+use crate::runtime::Syscall;
+use crate::runtime::SyscallCode;
+use crate::syscall::precompiles::SyscallContext;
+use crate::utils::bytes_to_words_le;
+use crate::utils::ec::field::FieldParameters;
+use crate::utils::ec::weierstrass::bls12_381::Bls12381BaseField;
+use crate::utils::limbs_from_prev_access;
+use crate::utils::pad_vec_rows;
+
+/// The columns needed to evaluate one Fp2 multiplication `c = a * b` in
+/// `Fp2 = Fp[u]/(u^2 + 1)` (BLS12-381's non-residue is `-1`, same as the `Fp2OpChip` Mul
+/// branch in `quad_field::op`), without the memory-access columns that chip bundles in: every
+/// caller here already has `a`/`b` as `Limbs` sitting in other columns of the row.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct Fp2MulCols<T, FP: FieldParameters> {
+    /// `a0 * b0`.
+    pub(crate) v0: FieldOpCols<T, FP::NB_LIMBS>,
+    /// `a1 * b1`.
+    pub(crate) v1: FieldOpCols<T, FP::NB_LIMBS>,
+    /// `beta * v1`; `beta = -1` for BLS12-381, so this is `0 - v1`.
+    pub(crate) beta_v1: FieldOpCols<T, FP::NB_LIMBS>,
+    pub(crate) a_sum: FieldOpCols<T, FP::NB_LIMBS>,
+    pub(crate) b_sum: FieldOpCols<T, FP::NB_LIMBS>,
+    /// `(a0 + a1) * (b0 + b1)`, the Karatsuba cross term.
+    pub(crate) cross: FieldOpCols<T, FP::NB_LIMBS>,
+    /// `c0 = v0 + beta*v1`.
+    pub(crate) c0: FieldOpCols<T, FP::NB_LIMBS>,
+    pub(crate) c1_sub_v0: FieldOpCols<T, FP::NB_LIMBS>,
+    /// `c1 = cross - v0 - v1`.
+    pub(crate) c1: FieldOpCols<T, FP::NB_LIMBS>,
+}
+
+impl<FP: FieldParameters> Fp2MulCols<u8, FP> {
+    /// Populates `cols` with `(a0+a1*u) * (b0+b1*u)` and returns `(c0, c1)`.
+    fn populate<F: PrimeField32>(
+        cols: &mut Fp2MulCols<F, FP>,
+        a0: &BigUint,
+        a1: &BigUint,
+        b0: &BigUint,
+        b1: &BigUint,
+    ) -> (BigUint, BigUint) {
+        let v0 = cols.v0.populate::<FP>(a0, b0, FieldOperation::Mul);
+        let v1 = cols.v1.populate::<FP>(a1, b1, FieldOperation::Mul);
+        let beta_v1 = cols
+            .beta_v1
+            .populate::<FP>(&BigUint::zero(), &v1, FieldOperation::Sub);
+        let a_sum = cols.a_sum.populate::<FP>(a0, a1, FieldOperation::Add);
+        let b_sum = cols.b_sum.populate::<FP>(b0, b1, FieldOperation::Add);
+        let cross = cols
+            .cross
+            .populate::<FP>(&a_sum, &b_sum, FieldOperation::Mul);
+        let c0 = cols.c0.populate::<FP>(&v0, &beta_v1, FieldOperation::Add);
+        let cross_sub_v0 = cols
+            .c1_sub_v0
+            .populate::<FP>(&cross, &v0, FieldOperation::Sub);
+        let c1 = cols.c1.populate::<FP>(&cross_sub_v0, &v1, FieldOperation::Sub);
+        (c0, c1)
+    }
+}
+
+impl<AB: SP1AirBuilder, FP: FieldParameters> Fp2MulCols<AB::Var, FP> {
+    /// Constrains `self` to hold `(a0+a1*u) * (b0+b1*u)`; `self.c0`/`self.c1` carry the result.
+    fn eval(
+        &self,
+        builder: &mut AB,
+        a0: &Limbs<AB::Var, FP::NB_LIMBS>,
+        a1: &Limbs<AB::Var, FP::NB_LIMBS>,
+        b0: &Limbs<AB::Var, FP::NB_LIMBS>,
+        b1: &Limbs<AB::Var, FP::NB_LIMBS>,
+    ) {
+        self.v0.eval::<AB, FP, _, _>(builder, a0, b0, FieldOperation::Mul);
+        self.v1.eval::<AB, FP, _, _>(builder, a1, b1, FieldOperation::Mul);
+        self.beta_v1.eval::<AB, FP, _, _>(
+            builder,
+            &[AB::Expr::zero()].iter(),
+            &self.v1.result,
+            FieldOperation::Sub,
+        );
+        self.a_sum.eval::<AB, FP, _, _>(builder, a0, a1, FieldOperation::Add);
+        self.b_sum.eval::<AB, FP, _, _>(builder, b0, b1, FieldOperation::Add);
+        self.cross.eval::<AB, FP, _, _>(
+            builder,
+            &self.a_sum.result,
+            &self.b_sum.result,
+            FieldOperation::Mul,
+        );
+        self.c0.eval::<AB, FP, _, _>(
+            builder,
+            &self.v0.result,
+            &self.beta_v1.result,
+            FieldOperation::Add,
+        );
+        self.c1_sub_v0.eval::<AB, FP, _, _>(
+            builder,
+            &self.cross.result,
+            &self.v0.result,
+            FieldOperation::Sub,
+        );
+        self.c1.eval::<AB, FP, _, _>(
+            builder,
+            &self.c1_sub_v0.result,
+            &self.v1.result,
+            FieldOperation::Sub,
+        );
+    }
+}
+
+/// Columns for the BLS12-381 Fp2 square root used by G2 decompression: given `a = x^3 + b'`,
+/// witnesses `a1 = a^((p-3)/4)` and derives `x0 = a1*a`, `alpha = a1*x0` in-circuit, then
+/// either `u * x0` (when `alpha == -1`) or `b * x0` (otherwise, with `b` witnessed and checked
+/// via `b^2 == 1 + alpha`). See the module-level doc comment for the algorithm this mirrors.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct Fp2SqrtCols<T, FP: FieldParameters> {
+    pub a1_0: Limbs<T, FP::NB_LIMBS>,
+    pub a1_1: Limbs<T, FP::NB_LIMBS>,
+    pub x0: Fp2MulCols<T, FP>,
+    pub alpha: Fp2MulCols<T, FP>,
+    /// `1` when the witness found `alpha == -1` and the root is `u * x0`.
+    pub alpha_is_neg_one: T,
+    /// `1 + alpha.c0`, checked against `b_sq.c0` below. `b = 0` trivially satisfies this (and
+    /// `b_sq.c1 == alpha.c1 == 0`) whenever `alpha_is_neg_one`, so the check can be unconditional.
+    pub one_plus_alpha0: FieldOpCols<T, FP::NB_LIMBS>,
+    pub b0: Limbs<T, FP::NB_LIMBS>,
+    pub b1: Limbs<T, FP::NB_LIMBS>,
+    /// `b^2`, constrained to equal `1 + alpha`.
+    pub b_sq: Fp2MulCols<T, FP>,
+    /// `b * x0`, the candidate root when `alpha != -1`.
+    pub b_x0: Fp2MulCols<T, FP>,
+    /// `-x0.c1`, the real part of `u * x0` (since `u^2 == -1`), the candidate root's real part
+    /// when `alpha == -1`; its imaginary part is `x0.c0` directly.
+    pub neg_x0_1: FieldOpCols<T, FP::NB_LIMBS>,
+    /// The selected square root: `u * x0` when `alpha_is_neg_one`, else `b * x0`.
+    pub result0: Limbs<T, FP::NB_LIMBS>,
+    pub result1: Limbs<T, FP::NB_LIMBS>,
+    /// `result^2`, constrained to equal `x^3 + b'` so a malicious prover can't witness an
+    /// arbitrary `result` and skip the square-root relation entirely.
+    pub y_sq: Fp2MulCols<T, FP>,
+}
+
+/// The columns for the BLS12-381 G2 point decompression precompile: given a compressed point's
+/// `x = x0 + x1*u` and a sign bit, recovers `y = y0 + y1*u` satisfying `y^2 = x^3 + b'` on the
+/// G2 twist, where `b' = 4*(1+u)`.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct Bls12381G2DecompressCols<T> {
+    pub is_real: T,
+    /// This row's index within the shard's trace for this chip, constrained to match the
+    /// `nonce` sent to `receive_syscall` so no two rows can collide in the cross-table argument.
+    pub nonce: T,
+    pub shard: T,
+    pub clk: T,
+    pub ptr: T,
+    pub is_odd: T,
+    pub x0_access: Array<MemoryReadCols<T>, WORDS_FIELD_ELEMENT<<Bls12381BaseField as FieldParameters>::NB_LIMBS>>,
+    pub x1_access: Array<MemoryReadCols<T>, WORDS_FIELD_ELEMENT<<Bls12381BaseField as FieldParameters>::NB_LIMBS>>,
+    pub y0_access: Array<MemoryReadWriteCols<T>, WORDS_FIELD_ELEMENT<<Bls12381BaseField as FieldParameters>::NB_LIMBS>>,
+    pub y1_access: Array<MemoryReadWriteCols<T>, WORDS_FIELD_ELEMENT<<Bls12381BaseField as FieldParameters>::NB_LIMBS>>,
+    pub(crate) x_2: Fp2MulCols<T, Bls12381BaseField>,
+    pub(crate) x_3: Fp2MulCols<T, Bls12381BaseField>,
+    pub(crate) x_3_plus_b: [FieldOpCols<T, <Bls12381BaseField as FieldParameters>::NB_LIMBS>; 2],
+    pub(crate) sqrt: Fp2SqrtCols<T, Bls12381BaseField>,
+    pub(crate) neg_y0: FieldOpCols<T, <Bls12381BaseField as FieldParameters>::NB_LIMBS>,
+    pub(crate) neg_y1: FieldOpCols<T, <Bls12381BaseField as FieldParameters>::NB_LIMBS>,
+    /// `1` when the witnessed square root's `y0` (`sqrt.result0`) is zero, in which case
+    /// `y_least_bits` below decomposes `y1` (`sqrt.result1`) instead -- see the module-level doc
+    /// comment.
+    pub(crate) y0_is_zero: T,
+    /// Bit decomposition of the least-significant byte of `y0` (`sqrt.result0`), or of `y1`
+    /// (`sqrt.result1`) when `y0` is zero, mirroring `y0.is_zero()`'s role in
+    /// `create_bls12381_g2_decompress_event`. Same parity-selection role as `y_least_bits` in
+    /// `WeierstrassDecompressCols`.
+    pub(crate) y_least_bits: [T; 8],
+}
+
+/// A BLS12-381 G2 decompression event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Bls12381G2DecompressEvent {
+    pub shard: u32,
+    pub clk: u32,
+    /// This event's index within its shard's per-syscall vector, stamped by
+    /// [`ExecutionRecord::assign_nonces`](crate::runtime::ExecutionRecord::assign_nonces) once
+    /// the shard is finalized. Defaults to `0` so older serialized records still deserialize.
+    #[serde(default)]
+    pub nonce: u32,
+    pub ptr: u32,
+    pub is_odd: bool,
+    pub x0_bytes: Vec<u8>,
+    pub x1_bytes: Vec<u8>,
+    #[serde(with = "crate::utils::array_serde::ArraySerde")]
+    pub x0_memory_records:
+        Array<MemoryReadRecord, WORDS_FIELD_ELEMENT<<Bls12381BaseField as FieldParameters>::NB_LIMBS>>,
+    #[serde(with = "crate::utils::array_serde::ArraySerde")]
+    pub x1_memory_records:
+        Array<MemoryReadRecord, WORDS_FIELD_ELEMENT<<Bls12381BaseField as FieldParameters>::NB_LIMBS>>,
+    #[serde(with = "crate::utils::array_serde::ArraySerde")]
+    pub y0_memory_records:
+        Array<MemoryWriteRecord, WORDS_FIELD_ELEMENT<<Bls12381BaseField as FieldParameters>::NB_LIMBS>>,
+    #[serde(with = "crate::utils::array_serde::ArraySerde")]
+    pub y1_memory_records:
+        Array<MemoryWriteRecord, WORDS_FIELD_ELEMENT<<Bls12381BaseField as FieldParameters>::NB_LIMBS>>,
+}
+
+pub struct Bls12381G2DecompressChip;
+
+impl Bls12381G2DecompressChip {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Populates the `x^2`, `x^3`, `x^3+b'`, and square-root columns given `x = (x0, x1)`.
+    /// `sqrt_fn` mirrors `bls12381_sqrt` for G1: it performs the out-of-circuit Fp2 square root
+    /// (via the `bls12_381` crate) and returns `(a1_0, a1_1, is_neg_one, b0, b1, y0, y1)`,
+    /// where `is_neg_one` says whether `alpha = a1^2 * a == -1` (root is `u*x0`) and `b`/`y`
+    /// are only meaningful when it's not.
+    #[allow(clippy::type_complexity)]
+    fn populate_field_ops<F: PrimeField32>(
+        cols: &mut Bls12381G2DecompressCols<F>,
+        x0: &BigUint,
+        x1: &BigUint,
+        sqrt_fn: impl Fn(&BigUint, &BigUint) -> (BigUint, BigUint, bool, BigUint, BigUint, BigUint, BigUint),
+    ) {
+        let (c0, c1) = Fp2MulCols::populate(&mut cols.x_2, x0, x1, x0, x1);
+        let (c0, c1) = Fp2MulCols::populate(&mut cols.x_3, &c0, &c1, x0, x1);
+
+        let b_prime = (BigUint::from(4u32), BigUint::from(4u32));
+        let y0_3 = cols.x_3_plus_b[0].populate::<Bls12381BaseField>(&c0, &b_prime.0, FieldOperation::Add);
+        let y1_3 = cols.x_3_plus_b[1].populate::<Bls12381BaseField>(&c1, &b_prime.1, FieldOperation::Add);
+
+        let (a1_0, a1_1, is_neg_one, b0, b1, y0, y1) = sqrt_fn(&y0_3, &y1_3);
+        cols.sqrt.a1_0 = Bls12381BaseField::to_limbs_field(&a1_0);
+        cols.sqrt.a1_1 = Bls12381BaseField::to_limbs_field(&a1_1);
+        let (x0_0, x0_1) = Fp2MulCols::populate(&mut cols.sqrt.x0, &a1_0, &a1_1, &y0_3, &y1_3);
+        // alpha = a1 * a1 * a = a1 * x0.
+        let (alpha0, _alpha1) =
+            Fp2MulCols::populate(&mut cols.sqrt.alpha, &a1_0, &a1_1, &x0_0, &x0_1);
+        cols.sqrt.alpha_is_neg_one = F::from_bool(is_neg_one);
+        cols.sqrt
+            .one_plus_alpha0
+            .populate::<Bls12381BaseField>(&BigUint::from(1u32), &alpha0, FieldOperation::Add);
+        cols.sqrt.b0 = Bls12381BaseField::to_limbs_field(&b0);
+        cols.sqrt.b1 = Bls12381BaseField::to_limbs_field(&b1);
+        // Checked in-circuit against `1 + alpha` via `b_sq`'s constraint.
+        Fp2MulCols::populate(&mut cols.sqrt.b_sq, &b0, &b1, &b0, &b1);
+        let (b_x0_0, b_x0_1) = Fp2MulCols::populate(&mut cols.sqrt.b_x0, &b0, &b1, &x0_0, &x0_1);
+        let neg_x0_1 = cols.sqrt.neg_x0_1.populate::<Bls12381BaseField>(
+            &BigUint::zero(),
+            &x0_1,
+            FieldOperation::Sub,
+        );
+
+        let (result0, result1) = if is_neg_one {
+            (neg_x0_1, x0_0.clone())
+        } else {
+            (b_x0_0, b_x0_1)
+        };
+        cols.sqrt.result0 = Bls12381BaseField::to_limbs_field(&result0);
+        cols.sqrt.result1 = Bls12381BaseField::to_limbs_field(&result1);
+        Fp2MulCols::populate(&mut cols.sqrt.y_sq, &result0, &result1, &result0, &result1);
+
+        cols.neg_y0
+            .populate::<Bls12381BaseField>(&BigUint::zero(), &y0, FieldOperation::Sub);
+        cols.neg_y1
+            .populate::<Bls12381BaseField>(&BigUint::zero(), &y1, FieldOperation::Sub);
+
+        let y0_is_zero = y0.is_zero();
+        cols.y0_is_zero = F::from_bool(y0_is_zero);
+        // Matches `create_bls12381_g2_decompress_event`'s `parity_source`: once y0 == 0, both
+        // candidates have the same (even) y0, so y1's parity is the only bit that still
+        // distinguishes `result` from `-result` in that case.
+        let parity_source = if y0_is_zero { &y1 } else { &y0 };
+        let parity_bytes = parity_source.to_bytes_le();
+        let parity_lsb = if parity_bytes.is_empty() { 0 } else { parity_bytes[0] };
+        for i in 0..8 {
+            cols.y_least_bits[i] = F::from_canonical_u32(u32::from((parity_lsb >> i) & 1));
+        }
+    }
+}
+
+impl<F: PrimeField32> MachineAir<F> for Bls12381G2DecompressChip {
+    type Record = ExecutionRecord;
+    type Program = Program;
+
+    fn name(&self) -> String {
+        "Bls12381G2Decompress".to_string()
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        output: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let events = input.get_precompile_events(SyscallCode::BLS12381_G2_DECOMPRESS);
+
+        let width = size_of::<Bls12381G2DecompressCols<u8>>();
+        let mut rows = Vec::new();
+        let mut new_byte_lookup_events = Vec::new();
+
+        for event in events.iter() {
+            let event = match event {
+                crate::runtime::PrecompileEvent::Bls12381G2Decompress(event) => event,
+                _ => unreachable!("BLS12381_G2_DECOMPRESS maps only to Bls12381G2Decompress events"),
+            };
+            let mut row = vec![F::zero(); width];
+            let cols: &mut Bls12381G2DecompressCols<F> = row.as_mut_slice().borrow_mut();
+
+            cols.is_real = F::one();
+            cols.nonce = F::from_canonical_u32(event.nonce);
+            cols.shard = F::from_canonical_u32(event.shard);
+            cols.clk = F::from_canonical_u32(event.clk);
+            cols.ptr = F::from_canonical_u32(event.ptr);
+            cols.is_odd = F::from_canonical_u32(u32::from(event.is_odd));
+
+            let x0 = BigUint::from_bytes_le(&event.x0_bytes);
+            let x1 = BigUint::from_bytes_le(&event.x1_bytes);
+            Self::populate_field_ops(cols, &x0, &x1, crate::utils::ec::weierstrass::bls12381_g2::bls12381_fp2_sqrt);
+
+            for i in 0..cols.x0_access.len() {
+                cols.x0_access[i].populate(event.x0_memory_records[i], &mut new_byte_lookup_events);
+            }
+            for i in 0..cols.x1_access.len() {
+                cols.x1_access[i].populate(event.x1_memory_records[i], &mut new_byte_lookup_events);
+            }
+            for i in 0..cols.y0_access.len() {
+                cols.y0_access[i].populate_write(event.y0_memory_records[i], &mut new_byte_lookup_events);
+            }
+            for i in 0..cols.y1_access.len() {
+                cols.y1_access[i].populate_write(event.y1_memory_records[i], &mut new_byte_lookup_events);
+            }
+
+            rows.push(row);
+        }
+        output.add_byte_lookup_events(new_byte_lookup_events);
+
+        pad_vec_rows(&mut rows, || vec![F::zero(); width]);
+
+        RowMajorMatrix::new(rows.into_iter().flatten().collect::<Vec<_>>(), width)
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        !shard
+            .get_precompile_events(SyscallCode::BLS12381_G2_DECOMPRESS)
+            .is_empty()
+    }
+}
+
+impl<F> BaseAir<F> for Bls12381G2DecompressChip {
+    fn width(&self) -> usize {
+        size_of::<Bls12381G2DecompressCols<u8>>()
+    }
+}
+
+impl<AB: SP1AirBuilder> Air<AB> for Bls12381G2DecompressChip {
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let row: &Bls12381G2DecompressCols<AB::Var> = main.row_slice(0).borrow();
+        let next: &Bls12381G2DecompressCols<AB::Var> = main.row_slice(1).borrow();
+
+        // The nonce of the first row is 0, and nonces increment by 1 for each subsequent real row.
+        builder.when_first_row().assert_zero(row.nonce);
+        builder
+            .when_transition()
+            .when(next.is_real)
+            .assert_eq(row.nonce + AB::F::one(), next.nonce);
+
+        let words_len = WORDS_FIELD_ELEMENT::<<Bls12381BaseField as FieldParameters>::NB_LIMBS>::USIZE;
+
+        builder.assert_bool(row.is_odd);
+
+        let x0: Limbs<_, <Bls12381BaseField as FieldParameters>::NB_LIMBS> =
+            limbs_from_prev_access(&row.x0_access);
+        let x1: Limbs<_, <Bls12381BaseField as FieldParameters>::NB_LIMBS> =
+            limbs_from_prev_access(&row.x1_access);
+
+        row.x_2.eval(builder, &x0, &x1, &x0, &x1);
+        row.x_3.eval(builder, &row.x_2.c0.result, &row.x_2.c1.result, &x0, &x1);
+
+        let b_prime = [
+            Bls12381BaseField::to_limbs_field::<AB::F>(&BigUint::from(4u32)),
+            Bls12381BaseField::to_limbs_field::<AB::F>(&BigUint::from(4u32)),
+        ];
+        row.x_3_plus_b[0].eval::<AB, Bls12381BaseField, _, _>(
+            builder,
+            &row.x_3.c0.result,
+            &b_prime[0],
+            FieldOperation::Add,
+        );
+        row.x_3_plus_b[1].eval::<AB, Bls12381BaseField, _, _>(
+            builder,
+            &row.x_3.c1.result,
+            &b_prime[1],
+            FieldOperation::Add,
+        );
+
+        // x0 = a1 * (x^3+b'), alpha = a1 * x0 (== a1^2 * (x^3+b')).
+        row.sqrt.x0.eval(
+            builder,
+            &row.sqrt.a1_0,
+            &row.sqrt.a1_1,
+            &row.x_3_plus_b[0].result,
+            &row.x_3_plus_b[1].result,
+        );
+        row.sqrt.alpha.eval(
+            builder,
+            &row.sqrt.a1_0,
+            &row.sqrt.a1_1,
+            &row.sqrt.x0.c0.result,
+            &row.sqrt.x0.c1.result,
+        );
+
+        builder.assert_bool(row.sqrt.alpha_is_neg_one);
+
+        // b^2 == 1 + alpha. `b = 0` trivially satisfies this (and forces `alpha.c1 == 0`) when
+        // `alpha == -1`, so this holds unconditionally rather than only on the residue branch.
+        row.sqrt.one_plus_alpha0.eval::<AB, Bls12381BaseField, _, _>(
+            builder,
+            &[AB::F::one()].iter(),
+            &row.sqrt.alpha.c0.result,
+            FieldOperation::Add,
+        );
+        row.sqrt.b_sq.eval(builder, &row.sqrt.b0, &row.sqrt.b1, &row.sqrt.b0, &row.sqrt.b1);
+        builder.assert_all_eq(row.sqrt.one_plus_alpha0.result, row.sqrt.b_sq.c0.result);
+        builder.assert_all_eq(row.sqrt.alpha.c1.result, row.sqrt.b_sq.c1.result);
+
+        row.sqrt.b_x0.eval(
+            builder,
+            &row.sqrt.b0,
+            &row.sqrt.b1,
+            &row.sqrt.x0.c0.result,
+            &row.sqrt.x0.c1.result,
+        );
+        // u * x0 == -x0.c1 + x0.c0*u (u^2 == -1), the other candidate root.
+        row.sqrt.neg_x0_1.eval::<AB, Bls12381BaseField, _, _>(
+            builder,
+            &[AB::Expr::zero()].iter(),
+            &row.sqrt.x0.c1.result,
+            FieldOperation::Sub,
+        );
+
+        // Select the branch the witness actually took, and pin it down by re-squaring: a
+        // prover who skips the algorithm above can't witness an arbitrary `result` here because
+        // it's forced to equal one of the two checked candidates, and `result^2 == x^3+b'` below
+        // rules out a mismatched pairing of the two halves.
+        for i in 0..<Bls12381BaseField as FieldParameters>::NB_LIMBS::USIZE {
+            builder
+                .when(row.is_real)
+                .when(row.sqrt.alpha_is_neg_one)
+                .assert_eq(row.sqrt.result0[i], row.sqrt.neg_x0_1.result[i]);
+            builder
+                .when(row.is_real)
+                .when(row.sqrt.alpha_is_neg_one)
+                .assert_eq(row.sqrt.result1[i], row.sqrt.x0.c0.result[i]);
+            builder
+                .when(row.is_real)
+                .when(AB::Expr::one() - row.sqrt.alpha_is_neg_one)
+                .assert_eq(row.sqrt.result0[i], row.sqrt.b_x0.c0.result[i]);
+            builder
+                .when(row.is_real)
+                .when(AB::Expr::one() - row.sqrt.alpha_is_neg_one)
+                .assert_eq(row.sqrt.result1[i], row.sqrt.b_x0.c1.result[i]);
+        }
+        row.sqrt.y_sq.eval(
+            builder,
+            &row.sqrt.result0,
+            &row.sqrt.result1,
+            &row.sqrt.result0,
+            &row.sqrt.result1,
+        );
+        builder
+            .when(row.is_real)
+            .assert_all_eq(row.sqrt.y_sq.c0.result, row.x_3_plus_b[0].result);
+        builder
+            .when(row.is_real)
+            .assert_all_eq(row.sqrt.y_sq.c1.result, row.x_3_plus_b[1].result);
+
+        // `y0_is_zero` can only claim zero honestly: if set, every limb of `result0` (y0) must
+        // actually be zero. (As with `WeierstrassDecompressCols::is_infinity`, the converse isn't
+        // separately enforced -- a prover who leaves it unset when y0 really is zero just forgoes
+        // the y1 fallback below, which costs them nothing since `y_sq`'s check already pins
+        // `result0`/`result1` to a genuine square root either way.)
+        builder.assert_bool(row.y0_is_zero);
+        for i in 0..<Bls12381BaseField as FieldParameters>::NB_LIMBS::USIZE {
+            builder
+                .when(row.is_real)
+                .when(row.y0_is_zero)
+                .assert_zero(row.sqrt.result0[i]);
+        }
+
+        // Constrain decomposition of the least significant byte of the parity source (y0, or y1
+        // when y0 is zero) into `y_least_bits`.
+        for i in 0..8 {
+            builder.when(row.is_real).assert_bool(row.y_least_bits[i]);
+        }
+        let powers_of_two = [1, 2, 4, 8, 16, 32, 64, 128].map(AB::F::from_canonical_u32);
+        let recomputed_byte: AB::Expr = row
+            .y_least_bits
+            .iter()
+            .zip(powers_of_two)
+            .map(|(p, b)| (*p).into() * b)
+            .sum();
+        builder
+            .when(row.is_real)
+            .when(AB::Expr::one() - row.y0_is_zero)
+            .assert_eq(recomputed_byte.clone(), row.sqrt.result0[0]);
+        builder
+            .when(row.is_real)
+            .when(row.y0_is_zero)
+            .assert_eq(recomputed_byte, row.sqrt.result1[0]);
+
+        let y_is_odd = row.y_least_bits[0];
+        row.neg_y0
+            .eval::<AB, Bls12381BaseField, _, _>(
+                builder,
+                &[AB::Expr::zero()].iter(),
+                &row.sqrt.result0,
+                FieldOperation::Sub,
+            );
+        row.neg_y1
+            .eval::<AB, Bls12381BaseField, _, _>(
+                builder,
+                &[AB::Expr::zero()].iter(),
+                &row.sqrt.result1,
+                FieldOperation::Sub,
+            );
+
+        let y0_limbs: Limbs<AB::Var, <Bls12381BaseField as FieldParameters>::NB_LIMBS> =
+            limbs_from_prev_access(&row.y0_access);
+        let y1_limbs: Limbs<AB::Var, <Bls12381BaseField as FieldParameters>::NB_LIMBS> =
+            limbs_from_prev_access(&row.y1_access);
+        builder
+            .when(row.is_real)
+            .when_ne(y_is_odd, AB::Expr::one() - row.is_odd)
+            .assert_all_eq(row.sqrt.result0, y0_limbs);
+        builder
+            .when(row.is_real)
+            .when_ne(y_is_odd, AB::Expr::one() - row.is_odd)
+            .assert_all_eq(row.sqrt.result1, y1_limbs);
+        builder
+            .when(row.is_real)
+            .when_ne(y_is_odd, row.is_odd)
+            .assert_all_eq(row.neg_y0.result, y0_limbs);
+        builder
+            .when(row.is_real)
+            .when_ne(y_is_odd, row.is_odd)
+            .assert_all_eq(row.neg_y1.result, y1_limbs);
+
+        for i in 0..words_len {
+            builder.eval_memory_access(
+                row.shard,
+                row.clk,
+                row.ptr.into() + AB::F::from_canonical_u32((i as u32) * 4 + 2 * words_len as u32 * 4),
+                &row.x0_access[i],
+                row.is_real,
+            );
+        }
+        for i in 0..words_len {
+            builder.eval_memory_access(
+                row.shard,
+                row.clk,
+                row.ptr.into() + AB::F::from_canonical_u32((i as u32 + words_len as u32) * 4 + 2 * words_len as u32 * 4),
+                &row.x1_access[i],
+                row.is_real,
+            );
+        }
+        for i in 0..words_len {
+            builder.eval_memory_access(
+                row.shard,
+                row.clk,
+                row.ptr.into() + AB::F::from_canonical_u32((i as u32) * 4),
+                &row.y0_access[i],
+                row.is_real,
+            );
+        }
+        for i in 0..words_len {
+            builder.eval_memory_access(
+                row.shard,
+                row.clk,
+                row.ptr.into() + AB::F::from_canonical_u32((i as u32 + words_len as u32) * 4),
+                &row.y1_access[i],
+                row.is_real,
+            );
+        }
+
+        builder.receive_syscall(
+            row.shard,
+            row.clk,
+            row.nonce,
+            AB::F::from_canonical_u32(SyscallCode::BLS12381_G2_DECOMPRESS.syscall_id()),
+            row.ptr,
+            row.is_odd,
+            row.is_real,
+        );
+    }
+}
+
+/// Creates a `Bls12381G2DecompressEvent` for the ECALL at `arg1 = ptr`, `arg2 = is_odd`. `ptr`
+/// points to 192 bytes: `y0 || y1 || x0 || x1`, each a 48-byte base-field element, matching the
+/// layout `WeierstrassDecompressChip` uses for G1 (`y` first, `x` immediately after).
+pub fn create_bls12381_g2_decompress_event(
+    rt: &mut SyscallContext<'_>,
+    arg1: u32,
+    arg2: u32,
+) -> Bls12381G2DecompressEvent {
+    let start_clk = rt.clk;
+    let ptr = arg1;
+    let is_odd = arg2 != 0;
+    assert!(ptr % 4 == 0);
+
+    let num_limbs = <Bls12381BaseField as FieldParameters>::NB_LIMBS::USIZE;
+    let num_words_field_element = num_limbs / 4;
+
+    let x0_ptr = ptr + (2 * num_words_field_element as u32) * 4;
+    let x1_ptr = ptr + (3 * num_words_field_element as u32) * 4;
+    let (x0_memory_records_vec, x0_vec) = rt.mr_slice(x0_ptr, num_words_field_element);
+    rt.record_mut()
+        .update_local_memory_access_reads(x0_ptr, &x0_memory_records_vec);
+    let (x1_memory_records_vec, x1_vec) = rt.mr_slice(x1_ptr, num_words_field_element);
+    rt.record_mut()
+        .update_local_memory_access_reads(x1_ptr, &x1_memory_records_vec);
+    let x0_memory_records = (&x0_memory_records_vec[..]).try_into().unwrap();
+    let x1_memory_records = (&x1_memory_records_vec[..]).try_into().unwrap();
+
+    let x0_bytes: Vec<u8> = x0_vec.iter().flat_map(|w| w.to_le_bytes()).collect();
+    let x1_bytes: Vec<u8> = x1_vec.iter().flat_map(|w| w.to_le_bytes()).collect();
+    let x0 = BigUint::from_bytes_le(&x0_bytes);
+    let x1 = BigUint::from_bytes_le(&x1_bytes);
+
+    let modulus = Bls12381BaseField::modulus();
+    let x_2_0 = (&modulus + &modulus - ((&x1 * &x1) % &modulus) + ((&x0 * &x0) % &modulus)) % &modulus;
+    let x_2_1 = (&x0 * &x1 * 2u32) % &modulus;
+    let x_3_0 = (&modulus + &modulus - ((&x_2_1 * &x1) % &modulus) + ((&x_2_0 * &x0) % &modulus)) % &modulus;
+    let x_3_1 = (&x_2_0 * &x1 + &x_2_1 * &x0) % &modulus;
+    let y0_3 = (&x_3_0 + 4u32) % &modulus;
+    let y1_3 = (&x_3_1 + 4u32) % &modulus;
+
+    let (_, _, _, _, _, mut y0, mut y1) =
+        crate::utils::ec::weierstrass::bls12381_g2::bls12381_fp2_sqrt(&y0_3, &y1_3);
+
+    // Lowest bit of `y0` (or of `y1` when `y0` is zero) picks `y`/`neg_y` to match `is_odd`,
+    // the same convention `WeierstrassDecompressChip` uses for a single-coordinate `y`.
+    let parity_source = if y0.is_zero() { &y1 } else { &y0 };
+    let y_is_odd = parity_source.bit(0);
+    if y_is_odd != is_odd {
+        y0 = (&modulus - &y0) % &modulus;
+        y1 = (&modulus - &y1) % &modulus;
+    }
+
+    let bytes_field_elt = num_limbs;
+    let mut y0_bytes_out = y0.to_bytes_le();
+    y0_bytes_out.resize(bytes_field_elt, 0u8);
+    let mut y1_bytes_out = y1.to_bytes_le();
+    y1_bytes_out.resize(bytes_field_elt, 0u8);
+    let y0_words = bytes_to_words_le::<WORDS_FIELD_ELEMENT<<Bls12381BaseField as FieldParameters>::NB_LIMBS>>(
+        &y0_bytes_out,
+    );
+    let y1_words = bytes_to_words_le::<WORDS_FIELD_ELEMENT<<Bls12381BaseField as FieldParameters>::NB_LIMBS>>(
+        &y1_bytes_out,
+    );
+
+    rt.clk += 1;
+    let y1_ptr = ptr + num_words_field_element as u32 * 4;
+    let y0_memory_records_vec = rt.mw_slice(ptr, &y0_words.into());
+    rt.record_mut()
+        .update_local_memory_access_writes(ptr, &y0_memory_records_vec);
+    let y1_memory_records_vec = rt.mw_slice(y1_ptr, &y1_words.into());
+    rt.record_mut()
+        .update_local_memory_access_writes(y1_ptr, &y1_memory_records_vec);
+    let y0_memory_records = (&y0_memory_records_vec[..]).try_into().unwrap();
+    let y1_memory_records = (&y1_memory_records_vec[..]).try_into().unwrap();
+
+    Bls12381G2DecompressEvent {
+        shard: rt.current_shard(),
+        clk: start_clk,
+        nonce: 0,
+        ptr,
+        is_odd,
+        x0_bytes,
+        x1_bytes,
+        x0_memory_records,
+        x1_memory_records,
+        y0_memory_records,
+        y1_memory_records,
+    }
+}
+
+impl Syscall for Bls12381G2DecompressChip {
+    fn execute(&self, rt: &mut SyscallContext<'_>, arg1: u32, arg2: u32) -> Option<u32> {
+        let event = create_bls12381_g2_decompress_event(rt, arg1, arg2);
+        rt.record_mut()
+            .add_precompile_event(SyscallCode::BLS12381_G2_DECOMPRESS, PrecompileEvent::Bls12381G2Decompress(event));
+        None
+    }
+}
@@ -30,7 +30,9 @@ use crate::runtime::SyscallCode;
 use crate::utils::bytes_to_words_le_vec;
 use crate::utils::ec::field::FieldParameters;
 use crate::utils::ec::weierstrass::bls12381::bls12381_sqrt;
+use crate::utils::ec::weierstrass::bn254::bn254_sqrt;
 use crate::utils::ec::weierstrass::secp256k1::secp256k1_sqrt;
+use crate::utils::ec::weierstrass::secp256r1::secp256r1_sqrt;
 use crate::utils::ec::weierstrass::WeierstrassParameters;
 use crate::utils::ec::{BaseLimbWidth, CurveType};
 use crate::utils::ec::{EllipticCurve, WithDecompression};
@@ -46,18 +48,39 @@ use crate::utils::pad_vec_rows;
 #[repr(C)]
 pub struct WeierstrassDecompressCols<T, U: LimbWidth = DEFAULT_NUM_LIMBS_T> {
     pub is_real: T,
+    /// This row's index within the shard's trace for this chip, constrained to match the
+    /// `nonce` sent to `receive_syscall` so no two rows can collide in the cross-table argument.
+    pub nonce: T,
     pub shard: T,
     pub clk: T,
     pub ptr: T,
     pub is_odd: T,
+    /// Whether the compressed point is the point at infinity, i.e. whether `x == 0`.
+    pub(crate) is_infinity: T,
     pub x_access: Array<MemoryReadCols<T>, WORDS_FIELD_ELEMENT<U>>,
     pub y_access: Array<MemoryReadWriteCols<T>, WORDS_FIELD_ELEMENT<U>>,
+    /// The value fed into the `x_2`/`x_3`/`a_x`/`ax_plus_b`/`x_3_plus_b`/`y`/`neg_y` chain below:
+    /// the real `x` from `x_access` when the point isn't infinity, or a fixed, always-valid dummy
+    /// (the curve generator's `x`) when it is. Without this, that chain would be forced to take a
+    /// square root of `b` itself on every legitimate infinity-encoded input (`x == 0`), which
+    /// panics in `populate` (and is unprovable in `eval`) for any curve where `b` isn't a
+    /// quadratic residue.
+    pub(crate) effective_x: Limbs<T, U>,
     pub(crate) x_2: FieldOpCols<T, U>,
     pub(crate) x_3: FieldOpCols<T, U>,
+    /// `a * x`, the curve's linear term. Zero for curves with `a = 0` (secp256k1, BLS12-381),
+    /// but nonzero for secp256r1 (`a = -3`), so it's always computed rather than special-cased.
+    pub(crate) a_x: FieldOpCols<T, U>,
+    pub(crate) ax_plus_b: FieldOpCols<T, U>,
     pub(crate) x_3_plus_b: FieldOpCols<T, U>,
     pub(crate) y: FieldSqrtCols<T, U>,
     pub(crate) neg_y: FieldOpCols<T, U>,
     pub(crate) y_least_bits: [T; 8],
+    /// The three BLS12-381 serialization flag bits packed into the top of the first compressed
+    /// byte: `compression` (always set, since this chip only ever handles compressed input),
+    /// `infinity` (mirrors `is_infinity`), and `sort` (mirrors `is_odd` when the point isn't
+    /// infinity, forced to zero when it is, per the standard encoding).
+    pub(crate) flags: [T; 3],
 }
 
 #[derive(Default)]
@@ -76,21 +99,47 @@ impl<E: EllipticCurve + WeierstrassParameters> WeierstrassDecompressChip<E> {
         cols: &mut WeierstrassDecompressCols<F, BaseLimbWidth<E>>,
         x: &BigUint,
     ) {
-        // Y = sqrt(x^3 + b)
-        let x_2 = cols
-            .x_2
-            .populate::<E::BaseField>(&x.clone(), &x.clone(), FieldOperation::Mul);
+        let is_infinity = x.is_zero();
+        cols.is_infinity = F::from_bool(is_infinity);
+        cols.flags = [
+            F::one(),
+            F::from_bool(is_infinity),
+            if is_infinity { F::zero() } else { cols.is_odd },
+        ];
+
+        // When the point is infinity, `x == 0` and `x^3 + a*x + b` reduces to `b`, which isn't
+        // guaranteed to be a quadratic residue for every curve this chip supports -- run the
+        // field-op chain on the curve generator's `x` instead in that case, the same fixed, known-
+        // good dummy the padding rows below already use, rather than square-rooting `b` itself.
+        let effective_x = if is_infinity { E::generator().0 } else { x.clone() };
+        cols.effective_x = E::BaseField::to_limbs_field::<F>(&effective_x);
+
+        // Y = sqrt(effective_x^3 + a*effective_x + b)
+        let x_2 = cols.x_2.populate::<E::BaseField>(
+            &effective_x.clone(),
+            &effective_x.clone(),
+            FieldOperation::Mul,
+        );
         let x_3 = cols
             .x_3
-            .populate::<E::BaseField>(&x_2, x, FieldOperation::Mul);
+            .populate::<E::BaseField>(&x_2, &effective_x, FieldOperation::Mul);
+        let a = E::a_int();
+        let a_x = cols
+            .a_x
+            .populate::<E::BaseField>(&a, &effective_x, FieldOperation::Mul);
         let b = E::b_int();
+        let ax_plus_b = cols
+            .ax_plus_b
+            .populate::<E::BaseField>(&a_x, &b, FieldOperation::Add);
         let x_3_plus_b = cols
             .x_3_plus_b
-            .populate::<E::BaseField>(&x_3, &b, FieldOperation::Add);
+            .populate::<E::BaseField>(&x_3, &ax_plus_b, FieldOperation::Add);
 
         let sqrt_fn = match E::CURVE_TYPE {
             CurveType::Secp256k1 => secp256k1_sqrt,
             CurveType::Bls12381 => bls12381_sqrt,
+            CurveType::Bn254 => bn254_sqrt,
+            CurveType::Secp256r1 => secp256r1_sqrt,
             _ => panic!("Unsupported curve"),
         };
         let y = cols.y.populate::<E::BaseField>(&x_3_plus_b, sqrt_fn);
@@ -118,6 +167,8 @@ impl<F: PrimeField32, E: EllipticCurve + WeierstrassParameters + WithDecompressi
         match E::CURVE_TYPE {
             CurveType::Secp256k1 => "Secp256k1Decompress".to_string(),
             CurveType::Bls12381 => "Bls12381Decompress".to_string(),
+            CurveType::Bn254 => "Bn254Decompress".to_string(),
+            CurveType::Secp256r1 => "Secp256r1Decompress".to_string(),
             _ => panic!("Unsupported curve"),
         }
     }
@@ -142,6 +193,7 @@ impl<F: PrimeField32, E: EllipticCurve + WeierstrassParameters + WithDecompressi
                 row.as_mut_slice().borrow_mut();
 
             cols.is_real = F::from_bool(true);
+            cols.nonce = F::from_canonical_usize(i);
             cols.shard = F::from_canonical_u32(event.shard);
             cols.clk = F::from_canonical_u32(event.clk);
             cols.ptr = F::from_canonical_u32(event.ptr);
@@ -187,11 +239,7 @@ impl<F: PrimeField32, E: EllipticCurve + WeierstrassParameters + WithDecompressi
     }
 
     fn included(&self, shard: &Self::Record) -> bool {
-        match E::CURVE_TYPE {
-            CurveType::Secp256k1 => !shard.secp256k1_decompress_events.is_empty(),
-            CurveType::Bls12381 => !shard.bls12381_decompress_events.is_empty(),
-            _ => panic!("Unsupported curve"),
-        }
+        !E::decompression_events(shard).is_empty()
     }
 }
 
@@ -210,23 +258,94 @@ where
         let main = builder.main();
         let row = main.row_slice(0);
         let row: &WeierstrassDecompressCols<AB::Var, BaseLimbWidth<E>> = (*row).borrow();
+        let next = main.row_slice(1);
+        let next: &WeierstrassDecompressCols<AB::Var, BaseLimbWidth<E>> = (*next).borrow();
+
+        // The nonce of the first row is 0, and nonces increment by 1 for each subsequent real row.
+        builder.when_first_row().assert_zero(row.nonce);
+        builder
+            .when_transition()
+            .when(next.is_real)
+            .assert_eq(row.nonce + AB::F::one(), next.nonce);
 
         let num_limbs = BaseLimbWidth::<E>::USIZE;
         let num_words_field_element = num_limbs / 4;
 
         builder.assert_bool(row.is_odd);
+        builder.assert_bool(row.is_infinity);
+        for flag in row.flags.iter() {
+            builder.assert_bool(*flag);
+        }
+
+        // The compression flag is always set (this chip only ever decompresses compressed
+        // points), the infinity flag mirrors `is_infinity`, and the sort flag mirrors `is_odd`
+        // except when the point is infinity, where the spec forces it to zero.
+        builder.when(row.is_real).assert_one(row.flags[0]);
+        builder
+            .when(row.is_real)
+            .assert_eq(row.flags[1], row.is_infinity);
+        builder
+            .when(row.is_real)
+            .when_ne(row.is_infinity, AB::Expr::zero())
+            .assert_zero(row.flags[2]);
+        builder
+            .when(row.is_real)
+            .when_ne(row.is_infinity, AB::Expr::one())
+            .assert_eq(row.flags[2], row.is_odd);
 
         let x: Limbs<AB::Var, BaseLimbWidth<E>> = limbs_from_prev_access(&row.x_access);
-        row.x_2
-            .eval::<AB, E::BaseField, _, _>(builder, &x, &x, FieldOperation::Mul);
-        row.x_3
-            .eval::<AB, E::BaseField, _, _>(builder, &row.x_2.result, &x, FieldOperation::Mul);
+
+        // `effective_x` feeds the `x_2`/`x_3`/`a_x`/`ax_plus_b`/`x_3_plus_b`/`y` chain below: the
+        // real `x` when the point isn't infinity, or the curve generator's `x` -- a fixed value
+        // that always satisfies the curve equation -- when it is. This keeps that chain from ever
+        // having to take a square root of `b` itself for the legitimate `x == 0` infinity
+        // encoding, which isn't a quadratic residue for every curve this chip supports.
+        let is_not_infinity = AB::Expr::one() - row.is_infinity.into();
+        let generator_const = E::BaseField::to_limbs_field::<AB::F>(&E::generator().0);
+        for i in 0..num_limbs {
+            builder
+                .when(row.is_real)
+                .when(is_not_infinity.clone())
+                .assert_eq(row.effective_x[i], x[i]);
+            builder
+                .when(row.is_real)
+                .when(row.is_infinity)
+                .assert_eq(row.effective_x[i], generator_const[i]);
+        }
+        let effective_x = row.effective_x;
+
+        row.x_2.eval::<AB, E::BaseField, _, _>(
+            builder,
+            &effective_x,
+            &effective_x,
+            FieldOperation::Mul,
+        );
+        row.x_3.eval::<AB, E::BaseField, _, _>(
+            builder,
+            &row.x_2.result,
+            &effective_x,
+            FieldOperation::Mul,
+        );
+        let a = E::a_int();
+        let a_const = E::BaseField::to_limbs_field::<AB::F>(&a);
+        row.a_x.eval::<AB, E::BaseField, _, _>(
+            builder,
+            &a_const,
+            &effective_x,
+            FieldOperation::Mul,
+        );
         let b = E::b_int();
         let b_const = E::BaseField::to_limbs_field::<AB::F>(&b);
+        row.ax_plus_b.eval::<AB, E::BaseField, _, _>(
+            builder,
+            &row.a_x.result,
+            &b_const,
+            FieldOperation::Add,
+        );
         row.x_3_plus_b.eval::<AB, E::BaseField, _, _>(
             builder,
             &row.x_3.result,
-            &b_const,
+            &row.ax_plus_b.result,
             FieldOperation::Add,
         );
         row.y
@@ -258,16 +377,37 @@ where
         let y_is_odd = row.y_least_bits[0];
 
         let y_limbs: Limbs<AB::Var, BaseLimbWidth<E>> = limbs_from_access(&row.y_access);
+
+        // When the point is infinity, the decompressed `y` is the all-zero word rather than a
+        // square root of `x^3 + a*x + b`; the odd/even selection below only applies otherwise.
         builder
             .when(row.is_real)
+            .when(is_not_infinity.clone())
             .when_ne(y_is_odd, AB::Expr::one() - row.is_odd)
             .assert_all_eq(row.y.multiplication.result, y_limbs);
 
         builder
             .when(row.is_real)
+            .when(is_not_infinity)
             .when_ne(y_is_odd, row.is_odd)
             .assert_all_eq(row.neg_y.result, y_limbs);
 
+        for i in 0..num_limbs {
+            builder
+                .when(row.is_real)
+                .when(row.is_infinity)
+                .assert_zero(y_limbs[i]);
+        }
+
+        // Tie `is_infinity` to the actual witnessed `x`: a prover can only skip the square-root
+        // checks above by claiming infinity when `x` really is the all-zero encoding.
+        for i in 0..num_limbs {
+            builder
+                .when(row.is_real)
+                .when(row.is_infinity)
+                .assert_zero(x[i]);
+        }
+
         for i in 0..num_words_field_element {
             builder.eval_memory_access(
                 row.shard,
@@ -293,12 +433,19 @@ where
             CurveType::Bls12381 => {
                 AB::F::from_canonical_u32(SyscallCode::BLS12381_DECOMPRESS.syscall_id())
             }
+            CurveType::Bn254 => {
+                AB::F::from_canonical_u32(SyscallCode::BN254_DECOMPRESS.syscall_id())
+            }
+            CurveType::Secp256r1 => {
+                AB::F::from_canonical_u32(SyscallCode::SECP256R1_DECOMPRESS.syscall_id())
+            }
             _ => panic!("Unsupported curve"),
         };
 
         builder.receive_syscall(
             row.shard,
             row.clk,
+            row.nonce,
             syscall_id,
             row.ptr,
             row.is_odd,
@@ -307,8 +454,10 @@ where
     }
 }
 
+// `pub(crate)` (rather than the usual bare `mod tests`) so `weierstrass_compress.rs` can reuse
+// `bls_decompress_risc_v_program` for a genuine compress-then-decompress round trip test.
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use crate::runtime::{Instruction, Opcode, SyscallCode};
     use crate::utils::tests::{BLS_DECOMPRESS_ELF, SECP256K1_DECOMPRESS_ELF};
     use crate::utils::{
@@ -324,10 +473,20 @@ mod tests {
     use rand::rngs::StdRng;
     use rand::SeedableRng;
 
-    fn bls_decompress_risc_v_program(w_ptr: u32, compressed_in: &[u8]) -> Program {
+    /// `is_odd` is the sign bit this precompile's own convention wants -- literal parity of the
+    /// decompressed `y` -- passed directly by the caller rather than inferred from
+    /// `compressed_in`'s wire-format "sort" bit. Those are different selection rules (sort bit:
+    /// the lexicographically larger root per the BLS12-381 serialization spec; `is_odd`: literal
+    /// parity of `y`) that only happen to agree for some points, so reinterpreting the wire's sort
+    /// bit as `is_odd` silently requests the wrong root whenever they disagree.
+    pub(crate) fn bls_decompress_risc_v_program(
+        w_ptr: u32,
+        compressed_in: &[u8],
+        is_odd: bool,
+    ) -> Program {
         assert_eq!(compressed_in.len(), 48);
 
-        let sign = (compressed_in[0] & 0b_0010_0000) >> 5 == 0;
+        let sign = is_odd;
         let mut compressed = compressed_in.to_owned();
         compressed[0] &= 0b_0001_1111;
 
@@ -366,6 +525,112 @@ mod tests {
         Program::new(instructions, 0, 0)
     }
 
+    /// Builds a program that writes `x_be` (big-endian) into the second half of a
+    /// `2 * x_be.len()`-byte buffer at `w_ptr` and calls `syscall_code` with `is_odd` passed
+    /// directly as the sign argument, mirroring `bls_decompress_risc_v_program` but for curves
+    /// whose compressed wire format has no flag byte to strip first.
+    fn weierstrass_decompress_risc_v_program(
+        syscall_code: SyscallCode,
+        w_ptr: u32,
+        x_be: &[u8],
+        is_odd: bool,
+    ) -> Program {
+        let num_limbs = x_be.len();
+
+        let mut instructions = vec![];
+
+        let mut words =
+            bytes_to_words_be_vec([x_be, &vec![0u8; num_limbs]].concat().as_slice());
+        words.reverse();
+
+        for i in 0..words.len() {
+            instructions.push(Instruction::new(Opcode::ADD, 29, 0, words[i], false, true));
+            instructions.push(Instruction::new(
+                Opcode::ADD,
+                30,
+                0,
+                w_ptr + (i as u32) * 4,
+                false,
+                true,
+            ));
+            instructions.push(Instruction::new(Opcode::SW, 29, 30, 0, false, true));
+        }
+
+        instructions.extend(vec![
+            Instruction::new(Opcode::ADD, 5, 0, syscall_code as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, w_ptr, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, u32::from(is_odd), false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+        ]);
+        Program::new(instructions, 0, 0)
+    }
+
+    #[test]
+    fn test_weierstrass_bn254_decompress() {
+        utils::setup_logger();
+
+        // The BN254 G1 generator: y^2 = x^3 + 3, and 2^2 == 1^3 + 3.
+        let mut x_be = [0u8; 32];
+        x_be[31] = 1;
+        let mut y_be = [0u8; 32];
+        y_be[31] = 2;
+        let is_odd = y_be[31] & 1 != 0;
+
+        let memory_pointer = 100u32;
+        let program = weierstrass_decompress_risc_v_program(
+            SyscallCode::BN254_DECOMPRESS,
+            memory_pointer,
+            &x_be,
+            is_odd,
+        );
+        let (_, memory) = run_test_with_memory_inspection(program);
+
+        let mut decompressed = vec![];
+        for i in 0..16 {
+            decompressed.push(memory.get(&(memory_pointer + i * 4)).unwrap().value);
+        }
+        let mut decompressed = words_to_bytes_le_vec(&decompressed);
+        decompressed.reverse();
+
+        assert_eq!(decompressed, [y_be, x_be].concat());
+    }
+
+    #[test]
+    fn test_weierstrass_secp256r1_decompress() {
+        utils::setup_logger();
+
+        // The NIST P-256 base point G.
+        let x_be: [u8; 32] = [
+            0x6b, 0x17, 0xd1, 0xf2, 0xe1, 0x2c, 0x42, 0x47, 0xf8, 0xbc, 0xe6, 0xe5, 0x63, 0xa4,
+            0x40, 0xf2, 0x77, 0x03, 0x7d, 0x81, 0x2d, 0xeb, 0x33, 0xa0, 0xf4, 0xa1, 0x39, 0x45,
+            0xd8, 0x98, 0xc2, 0x96,
+        ];
+        let y_be: [u8; 32] = [
+            0x4f, 0xe3, 0x42, 0xe2, 0xfe, 0x1a, 0x7f, 0x9b, 0x8e, 0xe7, 0xeb, 0x4a, 0x7c, 0x0f,
+            0x9e, 0x16, 0x2b, 0xce, 0x33, 0x57, 0x6b, 0x31, 0x5e, 0xce, 0xcb, 0xb6, 0x40, 0x68,
+            0x37, 0xbf, 0x51, 0xf5,
+        ];
+        let is_odd = y_be[31] & 1 != 0;
+
+        let memory_pointer = 100u32;
+        let program = weierstrass_decompress_risc_v_program(
+            SyscallCode::SECP256R1_DECOMPRESS,
+            memory_pointer,
+            &x_be,
+            is_odd,
+        );
+        let (_, memory) = run_test_with_memory_inspection(program);
+
+        let mut decompressed = vec![];
+        for i in 0..16 {
+            decompressed.push(memory.get(&(memory_pointer + i * 4)).unwrap().value);
+        }
+        let mut decompressed = words_to_bytes_le_vec(&decompressed);
+        decompressed.reverse();
+
+        assert_eq!(decompressed, [y_be, x_be].concat());
+    }
+
     const CANDIDATES: [[u8; 48]; 4] = [
         [
             128, 181, 135, 148, 52, 27, 78, 148, 13, 235, 10, 222, 148, 47, 2, 89, 248, 37, 76, 33,
@@ -389,39 +654,65 @@ mod tests {
         ],
     ];
 
-    // TODO: figure out why at some inputs this test fails
-    #[test]
-    fn test_weierstrass_bls_decompress_risc_v_program() {
+    /// Checks a single `CANDIDATES` entry against `bls_decompress_risc_v_program`. Factored out of
+    /// `test_weierstrass_bls_decompress_risc_v_program` so each candidate is tracked as its own
+    /// test instead of being silently excluded from a loop if one of them fails.
+    fn check_bls_decompress_risc_v_program_candidate(compressed_g1: &[u8; 48]) {
         utils::setup_logger();
 
-        // TODO: make this work on the last points CANDIDATES[2..]
-        for compressed_g1 in &CANDIDATES[..2] {
-            // use bls12_381 crate to compute expected value
-            let mut expected = G1Affine::from_compressed(compressed_g1)
-                .unwrap()
-                .to_uncompressed();
-            expected[0] &= 0b_0001_1111;
-
-            let memory_pointer = 100u32;
-            let program = bls_decompress_risc_v_program(memory_pointer, compressed_g1.as_ref());
-            let (_, memory) = run_test_with_memory_inspection(program);
-
-            let mut decompressed_g1 = vec![];
-            // decompressed G1 occupies 96 bytes or 24 words (8 bytes each): 96 / 8 = 24
-            for i in 0..24 {
-                decompressed_g1.push(memory.get(&(memory_pointer + i * 4)).unwrap().value);
-            }
+        // use bls12_381 crate to compute expected value
+        let mut expected = G1Affine::from_compressed(compressed_g1)
+            .unwrap()
+            .to_uncompressed();
+        expected[0] &= 0b_0001_1111;
+        let is_odd = expected[95] & 1 == 1;
+
+        let memory_pointer = 100u32;
+        let program =
+            bls_decompress_risc_v_program(memory_pointer, compressed_g1.as_ref(), is_odd);
+        let (_, memory) = run_test_with_memory_inspection(program);
+
+        let mut decompressed_g1 = vec![];
+        // decompressed G1 occupies 96 bytes or 24 words (4 bytes each): 96 / 4 = 24
+        for i in 0..24 {
+            decompressed_g1.push(memory.get(&(memory_pointer + i * 4)).unwrap().value);
+        }
 
-            let mut decompressed_g1 = words_to_bytes_le_vec(&decompressed_g1);
-            decompressed_g1.reverse();
+        let mut decompressed_g1 = words_to_bytes_le_vec(&decompressed_g1);
+        decompressed_g1.reverse();
 
-            assert_eq!(
-                decompressed_g1,
-                expected.to_vec(),
-                "Failed on {:?}",
-                compressed_g1
-            );
-        }
+        assert_eq!(
+            decompressed_g1,
+            expected.to_vec(),
+            "Failed on {:?}",
+            compressed_g1
+        );
+    }
+
+    #[test]
+    fn test_weierstrass_bls_decompress_risc_v_program_candidate_0() {
+        check_bls_decompress_risc_v_program_candidate(&CANDIDATES[0]);
+    }
+
+    #[test]
+    fn test_weierstrass_bls_decompress_risc_v_program_candidate_1() {
+        check_bls_decompress_risc_v_program_candidate(&CANDIDATES[1]);
+    }
+
+    // `CANDIDATES[2]`/`CANDIDATES[3]` were originally failing because
+    // `bls_decompress_risc_v_program` passed the compressed wire's own "sort" bit straight through
+    // as this chip's `is_odd` request. Those are different selection rules (sort bit: the
+    // lexicographically larger root; `is_odd`: literal parity of `y`), and they only happen to
+    // agree for `CANDIDATES[0]`/`CANDIDATES[1]`. Root-caused and fixed by deriving the requested
+    // bit from the real, canonical `y`'s parity instead -- see `bls_decompress_risc_v_program`.
+    #[test]
+    fn test_weierstrass_bls_decompress_risc_v_program_candidate_2() {
+        check_bls_decompress_risc_v_program_candidate(&CANDIDATES[2]);
+    }
+
+    #[test]
+    fn test_weierstrass_bls_decompress_risc_v_program_candidate_3() {
+        check_bls_decompress_risc_v_program_candidate(&CANDIDATES[3]);
     }
 
     #[test]
@@ -463,23 +754,50 @@ mod tests {
         assert_eq!(result, pt_uncompressed);
     }
 
-    #[test]
-    fn test_weierstrass_bls12381_decompress_candidates() {
+    /// Checks a single `CANDIDATES` entry through the full IO proof path. Factored out for the
+    /// same reason as `check_bls_decompress_risc_v_program_candidate` above: each candidate is
+    /// tracked as its own test rather than dropped from a loop.
+    fn check_bls12381_decompress_candidate(candidate: &[u8; 48]) {
         utils::setup_logger();
 
-        // TODO: figure out how to make this work on the last points CANDIDATES[2..]
-        for candidate in &CANDIDATES[..2] {
-            let pt_compressed = candidate;
-            let pt_affine = bls12_381::G1Affine::from_compressed(candidate).unwrap();
-            let pt_uncompressed = pt_affine.to_uncompressed();
+        let pt_compressed = candidate;
+        let pt_affine = bls12_381::G1Affine::from_compressed(candidate).unwrap();
+        let pt_uncompressed = pt_affine.to_uncompressed();
 
-            let inputs = SP1Stdin::from(&pt_compressed[..]);
+        let inputs = SP1Stdin::from(&pt_compressed[..]);
 
-            let mut proof = run_test_io(Program::from(BLS_DECOMPRESS_ELF), inputs).unwrap();
-            let mut result = [0; 96];
-            proof.public_values.read_slice(&mut result);
-            assert_eq!(result, pt_uncompressed);
-        }
+        let mut proof = run_test_io(Program::from(BLS_DECOMPRESS_ELF), inputs).unwrap();
+        let mut result = [0; 96];
+        proof.public_values.read_slice(&mut result);
+        assert_eq!(result, pt_uncompressed);
+    }
+
+    #[test]
+    fn test_weierstrass_bls12381_decompress_candidate_0() {
+        check_bls12381_decompress_candidate(&CANDIDATES[0]);
+    }
+
+    #[test]
+    fn test_weierstrass_bls12381_decompress_candidate_1() {
+        check_bls12381_decompress_candidate(&CANDIDATES[1]);
+    }
+
+    // Root cause: same sort-bit-vs-is_odd mismatch as
+    // `test_weierstrass_bls_decompress_risc_v_program_candidate_2/3` above, but here the
+    // mismatched bit is derived inside `BLS_DECOMPRESS_ELF` itself -- a prebuilt guest binary, not
+    // source in this tree -- so it can't be fixed from this chip or this file. Left `#[ignore]`d
+    // and tracked rather than silently dropped until that guest program is updated to derive its
+    // requested parity bit the same way `bls_decompress_risc_v_program` now does.
+    #[test]
+    #[ignore = "BLS_DECOMPRESS_ELF derives its requested parity bit from the wire's sort bit instead of the real y's parity; needs a guest-program fix outside this tree"]
+    fn test_weierstrass_bls12381_decompress_candidate_2() {
+        check_bls12381_decompress_candidate(&CANDIDATES[2]);
+    }
+
+    #[test]
+    #[ignore = "BLS_DECOMPRESS_ELF derives its requested parity bit from the wire's sort bit instead of the real y's parity; needs a guest-program fix outside this tree"]
+    fn test_weierstrass_bls12381_decompress_candidate_3() {
+        check_bls12381_decompress_candidate(&CANDIDATES[3]);
     }
 
     #[test]